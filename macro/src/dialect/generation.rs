@@ -88,6 +88,20 @@ pub fn generate_operation(operation: &Operation) -> TokenStream {
 
         #default_constructor
 
+        impl<'c> ::melior::ir::operation::NamedOperation<'c> for #identifier<'c> {
+            fn name() -> &'static str {
+                #operation_name
+            }
+
+            unsafe fn from_ref(operation: ::melior::ir::operation::OperationRef<'c, '_>) -> Self {
+                Self {
+                    operation: ::melior::ir::operation::Operation::from_raw(
+                        ::melior::ir::operation::OperationLike::to_raw(&operation),
+                    ),
+                }
+            }
+        }
+
         impl<'c> TryFrom<::melior::ir::operation::Operation<'c>> for #identifier<'c> {
             type Error = ::melior::Error;
 
@@ -49,6 +49,7 @@ static ATTRIBUTE_TYPES: LazyLock<HashMap<&'static str, &'static str>> = LazyLock
         FlatSymbolRefAttr => FlatSymbolRefAttribute,
         FloatAttr => FloatAttribute,
         IntegerAttr => IntegerAttribute,
+        IntegerSetAttr => IntegerSetAttribute,
         StringAttr => StringAttribute,
         TypeAttr => TypeAttribute,
     );
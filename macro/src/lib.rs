@@ -3,6 +3,7 @@ mod dialect;
 mod operation;
 mod parse;
 mod pass;
+mod pass_options;
 mod r#type;
 mod utility;
 
@@ -11,7 +12,7 @@ use parse::{DialectOperationSet, IdentifierList, PassSet};
 use proc_macro::TokenStream;
 use quote::quote;
 use std::error::Error;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, DeriveInput};
 
 /// Generates a dialect module from a TableGen file.
 ///
@@ -90,6 +91,34 @@ pub fn passes(stream: TokenStream) -> TokenStream {
     }))
 }
 
+/// Derives pass options parsed from pass pipelines.
+///
+/// Each field is an option named in kebab case and parsed with its
+/// [`FromStr`](std::str::FromStr) implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// use melior::pass::PassOptions;
+///
+/// #[derive(Default, melior::PassOptions)]
+/// struct Options {
+///     max_iterations: usize,
+///     verbose: bool,
+/// }
+///
+/// let options = Options::parse("max-iterations=4 verbose").unwrap();
+///
+/// assert_eq!(options.max_iterations, 4);
+/// assert!(options.verbose);
+/// ```
+#[proc_macro_derive(PassOptions)]
+pub fn pass_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    convert_result(pass_options::generate(input))
+}
+
 fn convert_result(result: Result<TokenStream, Box<dyn Error>>) -> TokenStream {
     result.unwrap_or_else(|error| {
         let message = error.to_string();
@@ -0,0 +1,50 @@
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use quote::quote;
+use std::error::Error;
+use syn::{Data, DeriveInput, Fields};
+
+pub fn generate(input: DeriveInput) -> Result<TokenStream, Box<dyn Error>> {
+    let Data::Struct(data) = &input.data else {
+        return Err("pass options must be a struct".into());
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err("pass options must have named fields".into());
+    };
+
+    let identifier = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = fields
+        .named
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let names = fields
+        .iter()
+        .map(|field| field.to_string().to_case(Case::Kebab))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::melior::pass::PassOptions for #identifier #type_generics #where_clause {
+            fn set_option(&mut self, name: &str, value: &str) -> Result<(), ::melior::Error> {
+                match name {
+                    #(#names => {
+                        self.#fields = value.parse().map_err(|_| {
+                            ::melior::Error::ParsePassPipeline(
+                                format!("invalid value of pass option {name}: {value}"),
+                            )
+                        })?;
+                    })*
+                    _ => {
+                        return Err(::melior::Error::ParsePassPipeline(
+                            format!("unknown pass option: {name}"),
+                        ))
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+    .into())
+}
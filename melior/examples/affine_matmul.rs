@@ -2,11 +2,13 @@
 //!
 //! This example demonstrates how to build an affine dialect matrix multiplication
 //! kernel using melior. It creates a function that multiplies two matrices A[M,K]
-//! and B[K,N] to produce C[M,N] using nested affine.for loops.
+//! and B[K,N] to produce C[M,N] using nested affine.for loops. The kernel is then
+//! lowered to the LLVM dialect and run with the execution engine.
 //!
 //! The generated MLIR code looks like:
 //! ```mlir
-//! func.func @matmul(%A: memref<4x8xf32>, %B: memref<8x16xf32>, %C: memref<4x16xf32>) {
+//! func.func @matmul(%A: memref<4x8xf32>, %B: memref<8x16xf32>, %C: memref<4x16xf32>)
+//!     attributes { llvm.emit_c_interface } {
 //!   affine.for %i = 0 to 4 {
 //!     affine.for %j = 0 to 16 {
 //!       affine.for %k = 0 to 8 {
@@ -29,10 +31,11 @@ use melior::{
         attribute::{IntegerAttribute, StringAttribute, TypeAttribute},
         operation::{OperationBuilder, OperationLike},
         r#type::{FunctionType, MemRefType},
-        Block, BlockLike, Identifier, Location, Module, Region, RegionLike, Type, Value,
+        Attribute, Block, BlockLike, Identifier, Location, Module, Region, RegionLike, Type, Value,
     },
-    utility::register_all_dialects,
-    Context,
+    pass::{self, PassManager},
+    utility::{register_all_dialects, register_all_llvm_translations},
+    Context, Error, ExecutionEngine, StridedMemRef,
 };
 
 /// Load all necessary dialects
@@ -41,6 +44,7 @@ fn load_dialects(context: &Context) {
     register_all_dialects(&registry);
     context.append_dialect_registry(&registry);
     context.load_all_available_dialects();
+    register_all_llvm_translations(context);
 }
 
 /// Creates an affine.for operation
@@ -144,11 +148,18 @@ fn build_matmul_module(m: i64, k: i64, n: i64) -> String {
     let context = Context::new();
     load_dialects(&context);
 
-    let location = Location::unknown(&context);
+    let module = build_matmul(&context, m, k, n);
+
+    module.as_operation().to_string()
+}
+
+/// Build a module with a matrix multiplication function
+fn build_matmul(context: &Context, m: i64, k: i64, n: i64) -> Module<'_> {
+    let location = Location::unknown(context);
     let module = Module::new(location);
 
-    let f32_type = Type::float32(&context);
-    let index_type = Type::index(&context);
+    let f32_type = Type::float32(context);
+    let index_type = Type::index(context);
     
     // Matrix types: A[M,K], B[K,N], C[M,N]
     let a_type = MemRefType::new(f32_type, &[m, k], None, None);
@@ -169,7 +180,7 @@ fn build_matmul_module(m: i64, k: i64, n: i64) -> String {
         let arg_c: Value = function_block.argument(2).unwrap().into();
 
         // Build nested loops
-        let i_loop = build_nested_loops(&context, arg_a, arg_b, arg_c, m, k, n, index_type, f32_type, location);
+        let i_loop = build_nested_loops(context, arg_a, arg_b, arg_c, m, k, n, index_type, f32_type, location);
         function_block.append_operation(i_loop);
 
         function_block.append_operation(func::r#return(&[], location));
@@ -178,18 +189,23 @@ fn build_matmul_module(m: i64, k: i64, n: i64) -> String {
         function_region.append_block(function_block);
 
         func::func(
-            &context,
-            StringAttribute::new(&context, "matmul"),
+            context,
+            StringAttribute::new(context, "matmul"),
             TypeAttribute::new(
                 FunctionType::new(
-                    &context,
+                    context,
                     &[a_type.into(), b_type.into(), c_type.into()],
                     &[],
                 )
                 .into(),
             ),
             function_region,
-            &[],
+            // Generate a C interface so that the function can be invoked
+            // through the execution engine.
+            &[(
+                Identifier::new(context, "llvm.emit_c_interface"),
+                Attribute::unit(context),
+            )],
             location,
         )
     };
@@ -201,10 +217,66 @@ fn build_matmul_module(m: i64, k: i64, n: i64) -> String {
         eprintln!("Module verification failed!");
     }
 
-    module.as_operation().to_string()
+    module
+}
+
+/// Lower a module from the affine dialect down to the LLVM dialect
+fn lower_to_llvm(context: &Context, module: &mut Module) -> Result<(), Error> {
+    let pass_manager = PassManager::new(context);
+
+    pass_manager.add_pass(pass::conversion::create_lower_affine());
+    pass_manager.add_pass(pass::conversion::create_scf_to_control_flow());
+    pass_manager.add_pass(pass::conversion::create_to_llvm());
+    pass_manager.add_pass(pass::conversion::create_reconcile_unrealized_casts());
+
+    pass_manager.run(module)
+}
+
+/// Compile the matmul kernel and run it on row-major matrices
+fn run_matmul(m: i64, k: i64, n: i64, a: &mut [f32], b: &mut [f32]) -> Result<Vec<f32>, Error> {
+    let context = Context::new();
+    load_dialects(&context);
+
+    let mut module = build_matmul(&context, m, k, n);
+    lower_to_llvm(&context, &mut module)?;
+
+    let engine = ExecutionEngine::new(&module, 2, &[], false);
+    let mut c = vec![0.0; (m * n) as usize];
+
+    // SAFETY: the argument types match the memref descriptors of the
+    // function signature, and the buffers outlive the call.
+    unsafe {
+        engine.invoke::<_, ()>(
+            "matmul",
+            (
+                StridedMemRef::new(a, [m, k]),
+                StridedMemRef::new(b, [k, n]),
+                StridedMemRef::new(&mut c, [m, n]),
+            ),
+        )?;
+    }
+
+    Ok(c)
+}
+
+/// Multiply matrices in plain Rust for comparison
+fn reference_matmul(m: i64, k: i64, n: i64, a: &[f32], b: &[f32]) -> Vec<f32> {
+    let (m, k, n) = (m as usize, k as usize, n as usize);
+    let mut c = vec![0.0; m * n];
+
+    for i in 0..m {
+        for j in 0..n {
+            for l in 0..k {
+                c[i * n + j] += a[i * k + l] * b[l * n + j];
+            }
+        }
+    }
+
+    c
 }
 
 /// Build the nested affine.for loops for matmul
+#[allow(clippy::too_many_arguments)]
 fn build_nested_loops<'c>(
     context: &'c Context,
     arg_a: Value<'c, '_>,
@@ -324,6 +396,20 @@ fn main() {
     
     let mlir_output = build_matmul_module(m, k, n);
     println!("{}", mlir_output);
+
+    let mut a = (0..m * k).map(|x| x as f32).collect::<Vec<_>>();
+    let mut b = (0..k * n).map(|x| (x % 3) as f32).collect::<Vec<_>>();
+    let expected = reference_matmul(m, k, n, &a, &b);
+
+    let c = run_matmul(m, k, n, &mut a, &mut b).expect("matmul runs");
+
+    for row in c.chunks(n as usize) {
+        println!("{:?}", row);
+    }
+
+    assert_eq!(c, expected);
+    println!();
+    println!("Result matches the reference implementation.");
 }
 
 #[cfg(test)]
@@ -348,4 +434,13 @@ mod tests {
         assert!(output.contains("memref<3x4xf32>"));
         assert!(output.contains("memref<2x4xf32>"));
     }
+
+    #[test]
+    fn test_run_matmul() {
+        let mut a = (0..6).map(|x| x as f32).collect::<Vec<_>>();
+        let mut b = (0..12).map(|x| (x % 5) as f32).collect::<Vec<_>>();
+        let expected = reference_matmul(2, 3, 4, &a, &b);
+
+        assert_eq!(run_matmul(2, 3, 4, &mut a, &mut b), Ok(expected));
+    }
 }
@@ -6,7 +6,8 @@ use mlir_sys::{
     mlirGetDialectHandle__func__, mlirGetDialectHandle__gpu__, mlirGetDialectHandle__linalg__,
     mlirGetDialectHandle__llvm__, mlirGetDialectHandle__pdl__, mlirGetDialectHandle__quant__,
     mlirGetDialectHandle__scf__, mlirGetDialectHandle__shape__,
-    mlirGetDialectHandle__sparse_tensor__, mlirGetDialectHandle__tensor__, MlirDialectHandle,
+    mlirGetDialectHandle__sparse_tensor__, mlirGetDialectHandle__tensor__,
+    mlirGetDialectHandle__transform__, MlirDialectHandle,
 };
 
 /// A dialect handle.
@@ -76,6 +77,11 @@ impl DialectHandle {
         unsafe { Self::from_raw(mlirGetDialectHandle__tensor__()) }
     }
 
+    /// Creates a `transform` dialect handle.
+    pub fn transform() -> Self {
+        unsafe { Self::from_raw(mlirGetDialectHandle__transform__()) }
+    }
+
     /// Returns a namespace.
     pub fn namespace(&self) -> StringRef<'_> {
         unsafe { StringRef::from_raw(mlirDialectHandleGetNamespace(self.raw)) }
@@ -125,6 +131,14 @@ mod tests {
         DialectHandle::llvm();
     }
 
+    #[test]
+    fn transform() {
+        assert_eq!(
+            DialectHandle::transform().namespace().as_str(),
+            Ok("transform")
+        );
+    }
+
     #[test]
     fn namespace() {
         DialectHandle::func().namespace();
@@ -0,0 +1,176 @@
+//! `transform` dialect.
+
+use crate::{
+    ir::{operation::OperationLike, Module, OperationRef},
+    logical_result::LogicalResult,
+    Error,
+};
+use mlir_sys::{
+    mlirTransformApplyNamedSequence, mlirTransformOptionsCreate, mlirTransformOptionsDestroy,
+    mlirTransformOptionsEnableExpensiveChecks,
+    mlirTransformOptionsEnforceSingleTopLevelTransformOp,
+    mlirTransformOptionsGetEnforceSingleTopLevelTransformOp,
+    mlirTransformOptionsGetExpensiveChecksEnabled, MlirTransformOptions,
+};
+
+/// Options of the transform dialect interpreter.
+#[derive(Debug)]
+pub struct TransformOptions {
+    raw: MlirTransformOptions,
+}
+
+impl TransformOptions {
+    /// Creates transform options.
+    pub fn new() -> Self {
+        Self {
+            raw: unsafe { mlirTransformOptionsCreate() },
+        }
+    }
+
+    /// Enables expensive checks of transform IR and payload IR.
+    pub fn enable_expensive_checks(&mut self, enabled: bool) -> &mut Self {
+        unsafe { mlirTransformOptionsEnableExpensiveChecks(self.raw, enabled) }
+        self
+    }
+
+    /// Returns `true` if expensive checks are enabled.
+    pub fn expensive_checks_enabled(&self) -> bool {
+        unsafe { mlirTransformOptionsGetExpensiveChecksEnabled(self.raw) }
+    }
+
+    /// Enforces that there is only one top-level transform operation.
+    pub fn enforce_single_top_level_transform_operation(&mut self, enabled: bool) -> &mut Self {
+        unsafe { mlirTransformOptionsEnforceSingleTopLevelTransformOp(self.raw, enabled) }
+        self
+    }
+
+    /// Returns `true` if there must be only one top-level transform operation.
+    pub fn single_top_level_transform_operation_enforced(&self) -> bool {
+        unsafe { mlirTransformOptionsGetEnforceSingleTopLevelTransformOp(self.raw) }
+    }
+
+    /// Converts transform options into a raw object.
+    pub const fn to_raw(&self) -> MlirTransformOptions {
+        self.raw
+    }
+}
+
+impl Drop for TransformOptions {
+    fn drop(&mut self) {
+        unsafe { mlirTransformOptionsDestroy(self.raw) }
+    }
+}
+
+impl Default for TransformOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Applies a transform named sequence to payload IR with the transform
+/// dialect interpreter.
+///
+/// Symbols referenced by the named sequence are resolved in an operation
+/// which contains it, or in the named sequence itself if it has no parent.
+pub fn apply_named_sequence(
+    payload: &mut Module,
+    transform_root: OperationRef,
+    options: &TransformOptions,
+) -> Result<(), Error> {
+    let transform_module = transform_root.parent_operation().unwrap_or(transform_root);
+
+    let result = LogicalResult::from_raw(unsafe {
+        mlirTransformApplyNamedSequence(
+            payload.as_operation().to_raw(),
+            transform_root.to_raw(),
+            transform_module.to_raw(),
+            options.to_raw(),
+        )
+    });
+
+    if result.is_success() {
+        Ok(())
+    } else {
+        Err(Error::ApplyTransform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir::BlockLike, test::create_test_context};
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn options() {
+        let mut options = TransformOptions::new();
+
+        options
+            .enable_expensive_checks(false)
+            .enforce_single_top_level_transform_operation(false);
+
+        assert!(!options.expensive_checks_enabled());
+        assert!(!options.single_top_level_transform_operation_enforced());
+
+        options.enable_expensive_checks(true);
+
+        assert!(options.expensive_checks_enabled());
+    }
+
+    #[test]
+    fn apply() {
+        let context = create_test_context();
+        let mut payload = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 : i32
+                    return %0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let transform = Module::parse(
+            &context,
+            indoc!(
+                r#"
+                module attributes {transform.with_named_sequence} {
+                  transform.named_sequence @__transform_main(%root: !transform.any_op {transform.readonly}) {
+                    %addition = transform.structured.match ops{["arith.addi"]} in %root : (!transform.any_op) -> !transform.any_op
+                    transform.annotate %addition "annotated" : !transform.any_op
+                    transform.yield
+                  }
+                }
+                "#
+            ),
+        )
+        .unwrap();
+        let mut options = TransformOptions::new();
+        options.enable_expensive_checks(true);
+
+        apply_named_sequence(
+            &mut payload,
+            transform.body().first_operation().unwrap(),
+            &options,
+        )
+        .unwrap();
+
+        assert!(payload.as_operation().verify());
+        assert_eq!(
+            payload.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i32, %arg1: i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 {annotated} : i32
+                    return %0 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+}
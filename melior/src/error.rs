@@ -2,40 +2,60 @@ use std::{
     convert::Infallible,
     error,
     fmt::{self, Display, Formatter},
+    io,
     str::Utf8Error,
 };
 
 /// A Melior error.
 #[derive(Debug, Eq, PartialEq)]
 pub enum Error {
+    ApplyPatterns,
+    ApplyTransform,
     AttributeExpected(&'static str, String),
     AttributeNotFound(String),
     AttributeParse(String),
     BlockArgumentExpected(String),
+    ConvertType(String),
+    CreateExecutionEngine(String),
     ElementExpected {
         r#type: &'static str,
         value: String,
     },
+    FunctionNotFound(String),
+    FunctionTypeMismatch {
+        name: String,
+        r#type: String,
+    },
+    InstrumentationAfterPasses(String),
     InvokeFunction,
+    Io(String),
+    LegalizeOperation(String),
     OperationBuild,
     OperandNotFound(&'static str),
+    OperationNotInBlock(String),
     OperationResultExpected(String),
     PositionOutOfBounds {
         name: &'static str,
         value: String,
         index: usize,
     },
+    ParentRegionNotFound(String),
     ParsePassPipeline(String),
+    ReplaceSymbolUses(String),
     ResultNotFound(&'static str),
     RunPass,
+    TranslateToLlvmIr,
     TypeExpected(&'static str, String),
     UnknownDiagnosticSeverity(u32),
     Utf8(Utf8Error),
+    WriteBytecode,
 }
 
 impl Display for Error {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
+            Self::ApplyPatterns => write!(formatter, "failed to apply rewrite patterns"),
+            Self::ApplyTransform => write!(formatter, "failed to apply transform"),
             Self::AttributeExpected(r#type, attribute) => {
                 write!(formatter, "{type} attribute expected: {attribute}")
             }
@@ -48,29 +68,65 @@ impl Display for Error {
             Self::BlockArgumentExpected(value) => {
                 write!(formatter, "block argument expected: {value}")
             }
+            Self::ConvertType(r#type) => {
+                write!(formatter, "failed to convert type: {type}")
+            }
+            Self::CreateExecutionEngine(message) => {
+                write!(formatter, "failed to create execution engine")?;
+
+                if !message.is_empty() {
+                    write!(formatter, ":\n{message}")?;
+                }
+
+                Ok(())
+            }
             Self::ElementExpected { r#type, value } => {
                 write!(formatter, "element of {type} type expected: {value}")
             }
+            Self::FunctionNotFound(name) => write!(formatter, "function {name} not found"),
+            Self::FunctionTypeMismatch { name, r#type } => {
+                write!(formatter, "function {name} does not match type {type}")
+            }
+            Self::InstrumentationAfterPasses(pipeline) => {
+                write!(
+                    formatter,
+                    "instrumentation must be added before passes: {pipeline}"
+                )
+            }
             Self::InvokeFunction => write!(formatter, "failed to invoke JIT-compiled function"),
+            Self::Io(message) => write!(formatter, "{message}"),
+            Self::LegalizeOperation(name) => {
+                write!(formatter, "failed to legalize operation: {name}")
+            }
             Self::OperationBuild => {
                 write!(formatter, "operation build failed")
             }
             Self::OperandNotFound(name) => {
                 write!(formatter, "operand {name} not found")
             }
+            Self::OperationNotInBlock(operation) => {
+                write!(formatter, "operation not in block: {operation}")
+            }
             Self::OperationResultExpected(value) => {
                 write!(formatter, "operation result expected: {value}")
             }
+            Self::ParentRegionNotFound(block) => {
+                write!(formatter, "parent region not found: {block}")
+            }
             Self::ParsePassPipeline(message) => {
                 write!(formatter, "failed to parse pass pipeline:\n{message}")
             }
             Self::PositionOutOfBounds { name, value, index } => {
                 write!(formatter, "{name} position {index} out of bounds: {value}")
             }
+            Self::ReplaceSymbolUses(name) => {
+                write!(formatter, "failed to replace uses of symbol {name}")
+            }
             Self::ResultNotFound(name) => {
                 write!(formatter, "result {name} not found")
             }
             Self::RunPass => write!(formatter, "failed to run pass"),
+            Self::TranslateToLlvmIr => write!(formatter, "failed to translate module to LLVM IR"),
             Self::TypeExpected(r#type, actual) => {
                 write!(formatter, "{type} type expected: {actual}")
             }
@@ -80,6 +136,7 @@ impl Display for Error {
             Self::Utf8(error) => {
                 write!(formatter, "{error}")
             }
+            Self::WriteBytecode => write!(formatter, "failed to write bytecode"),
         }
     }
 }
@@ -92,6 +149,12 @@ impl From<Utf8Error> for Error {
     }
 }
 
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.to_string())
+    }
+}
+
 impl From<Infallible> for Error {
     fn from(_: Infallible) -> Self {
         unreachable!()
@@ -1,37 +1,94 @@
-use crate::{ir::Module, logical_result::LogicalResult, string_ref::StringRef, Error};
+mod function;
+mod jit;
+mod memref;
+
+pub use self::{
+    function::{JitClosure, JitFunction},
+    jit::{JitArgument, JitResult, JitReturn, JitType},
+    memref::{StridedMemRef, UnrankedMemRef},
+};
+use crate::{
+    ir::{r#type::FunctionType, Module},
+    logical_result::LogicalResult,
+    string_ref::StringRef,
+    Error,
+};
 use mlir_sys::{
     mlirExecutionEngineCreate, mlirExecutionEngineDestroy, mlirExecutionEngineDumpToObjectFile,
-    mlirExecutionEngineInvokePacked, mlirExecutionEngineLookup, mlirExecutionEngineRegisterSymbol,
-    MlirExecutionEngine,
+    mlirExecutionEngineInvokePacked, mlirExecutionEngineLookup, mlirExecutionEngineLookupPacked,
+    mlirExecutionEngineRegisterSymbol, MlirExecutionEngine,
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    mem::{align_of, size_of},
 };
 
 /// An execution engine.
 pub struct ExecutionEngine {
     raw: MlirExecutionEngine,
+    closures: RefCell<Vec<Box<dyn Any>>>,
 }
 
 impl ExecutionEngine {
     /// Creates an execution engine.
+    ///
+    /// # Panics
+    ///
+    /// Panics with diagnostics emitted during its creation if the module
+    /// cannot be compiled. Use [`try_new`](Self::try_new) to handle the failure.
     pub fn new(
         module: &Module,
         optimization_level: usize,
         shared_library_paths: &[&str],
         enable_object_dump: bool,
     ) -> Self {
-        Self {
-            raw: unsafe {
-                mlirExecutionEngineCreate(
-                    module.to_raw(),
-                    optimization_level as i32,
-                    shared_library_paths.len() as i32,
-                    shared_library_paths
-                        .iter()
-                        .map(|&string| StringRef::new(string).to_raw())
-                        .collect::<Vec<_>>()
-                        .as_ptr(),
-                    enable_object_dump,
-                )
-            },
+        Self::try_new(
+            module,
+            optimization_level,
+            shared_library_paths,
+            enable_object_dump,
+        )
+        .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates an execution engine.
+    ///
+    /// It returns an error with diagnostics emitted during its creation if the
+    /// module cannot be compiled, e.g. when it is not lowered to the `llvm`
+    /// dialect. The diagnostics are not propagated to other diagnostic
+    /// handlers attached to the context.
+    pub fn try_new(
+        module: &Module,
+        optimization_level: usize,
+        shared_library_paths: &[&str],
+        enable_object_dump: bool,
+    ) -> Result<Self, Error> {
+        let context = module.context();
+        let context = unsafe { context.to_ref() };
+        let mut diagnostics = vec![];
+
+        let id = context.attach_diagnostic_handler(|diagnostic| {
+            diagnostics.push(diagnostic.to_string());
+            true
+        });
+
+        let raw = create_raw(
+            module,
+            optimization_level,
+            shared_library_paths,
+            enable_object_dump,
+        );
+
+        context.detach_diagnostic_handler(id);
+
+        if raw.ptr.is_null() {
+            Err(Error::CreateExecutionEngine(diagnostics.join("\n")))
+        } else {
+            Ok(Self {
+                raw,
+                closures: Default::default(),
+            })
         }
     }
 
@@ -40,6 +97,48 @@ impl ExecutionEngine {
         unsafe { mlirExecutionEngineLookup(self.raw, StringRef::new(name).to_raw()) as *mut () }
     }
 
+    /// Searches a packed wrapper of a function in a module and returns a
+    /// pointer to it.
+    pub fn lookup_packed(&self, name: &str) -> *mut () {
+        unsafe {
+            mlirExecutionEngineLookupPacked(self.raw, StringRef::new(name).to_raw()) as *mut ()
+        }
+    }
+
+    /// Invokes a function in a module with typed arguments and returns its
+    /// results.
+    ///
+    /// Multiple results are returned as a tuple. It returns an error if the
+    /// function is not found.
+    ///
+    /// # Safety
+    ///
+    /// The `A` and `R` types must match types of arguments and results of the
+    /// function respectively.
+    pub unsafe fn invoke<A: JitArgument, R: JitReturn>(
+        &self,
+        name: &str,
+        mut arguments: A,
+    ) -> Result<R, Error> {
+        if self.lookup_packed(name).is_null() {
+            return Err(Error::FunctionNotFound(name.into()));
+        }
+
+        let layout = R::layout();
+        // Results are stored in a buffer aligned for any scalar types.
+        let mut results = vec![0u128; layout.size().div_ceil(size_of::<u128>())];
+        let mut pointers = vec![];
+
+        assert!(layout.align() <= align_of::<u128>());
+
+        arguments.push_pointers(&mut pointers);
+        pointers.push(results.as_mut_ptr() as *mut ());
+
+        self.invoke_packed(name, &mut pointers)?;
+
+        Ok(R::read(results.as_ptr() as *const u8))
+    }
+
     /// Invokes a function in a module. The `arguments` argument includes
     /// pointers to results of the function as well as arguments.
     ///
@@ -73,6 +172,49 @@ impl ExecutionEngine {
         mlirExecutionEngineRegisterSymbol(self.raw, StringRef::new(name).to_raw(), ptr as _);
     }
 
+    /// Registers a function as a symbol accessible to JIT-compiled functions.
+    ///
+    /// If a function type is given, it returns an error when the types of
+    /// arguments and results of the function do not match the type.
+    pub fn register_function<F: JitFunction>(
+        &self,
+        name: &str,
+        function: F,
+        r#type: Option<FunctionType>,
+    ) -> Result<(), Error> {
+        check_function_type(name, r#type, F::matches)?;
+
+        unsafe { self.register_symbol(name, function.to_pointer()) };
+
+        Ok(())
+    }
+
+    /// Registers a closure as a symbol accessible to JIT-compiled functions.
+    ///
+    /// The symbol refers to a trampoline function that takes a user data
+    /// pointer as its first argument followed by arguments of the closure. It
+    /// returns the user data pointer, which JIT-compiled functions must pass
+    /// to the trampoline. The closure lives as long as the execution engine.
+    ///
+    /// If a function type is given, it returns an error when the types of
+    /// arguments and results of the trampoline do not match the type.
+    pub fn register_closure<A, R, F: JitClosure<A, R> + 'static>(
+        &self,
+        name: &str,
+        closure: F,
+        r#type: Option<FunctionType>,
+    ) -> Result<*mut (), Error> {
+        check_function_type(name, r#type, F::matches)?;
+
+        let closure = Box::new(closure);
+        let data = &*closure as *const F as *mut ();
+
+        unsafe { self.register_symbol(name, F::trampoline()) };
+        self.closures.borrow_mut().push(closure);
+
+        Ok(data)
+    }
+
     /// Dumps a module to an object file.
     pub fn dump_to_object_file(&self, path: &str) {
         unsafe { mlirExecutionEngineDumpToObjectFile(self.raw, StringRef::new(path).to_raw()) }
@@ -85,10 +227,49 @@ impl Drop for ExecutionEngine {
     }
 }
 
+fn create_raw(
+    module: &Module,
+    optimization_level: usize,
+    shared_library_paths: &[&str],
+    enable_object_dump: bool,
+) -> MlirExecutionEngine {
+    unsafe {
+        mlirExecutionEngineCreate(
+            module.to_raw(),
+            optimization_level as i32,
+            shared_library_paths.len() as i32,
+            shared_library_paths
+                .iter()
+                .map(|&string| StringRef::new(string).to_raw())
+                .collect::<Vec<_>>()
+                .as_ptr(),
+            enable_object_dump,
+        )
+    }
+}
+
+fn check_function_type(
+    name: &str,
+    r#type: Option<FunctionType>,
+    matches: impl Fn(FunctionType) -> bool,
+) -> Result<(), Error> {
+    match r#type {
+        Some(r#type) if !matches(r#type) => Err(Error::FunctionTypeMismatch {
+            name: name.into(),
+            r#type: r#type.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{pass, test::create_test_context};
+    use crate::{
+        ir::{r#type::IntegerType, Location},
+        pass,
+        test::create_test_context,
+    };
 
     #[test]
     fn invoke_packed() {
@@ -134,6 +315,273 @@ mod tests {
         assert_eq!(result, 84);
     }
 
+    #[test]
+    fn invoke() {
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @add(%arg0 : i64, %arg1 : f64) -> (i64, f64) attributes { llvm.emit_c_interface } {
+                    %0 = arith.addi %arg0, %arg0 : i64
+                    %1 = arith.addf %arg1, %arg1 : f64
+                    return %0, %1 : i64, f64
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        let engine = ExecutionEngine::new(&module, 2, &[], false);
+
+        assert_eq!(
+            unsafe { engine.invoke::<_, (i64, f64)>("add", (42i64, 1.5f64)) },
+            Ok((84, 3.0))
+        );
+    }
+
+    #[test]
+    fn invoke_missing_function() {
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @foo() attributes { llvm.emit_c_interface } {
+                    return
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        let engine = ExecutionEngine::new(&module, 2, &[], false);
+
+        assert_eq!(unsafe { engine.invoke::<_, ()>("foo", ()) }, Ok(()));
+        assert_eq!(
+            unsafe { engine.invoke::<_, ()>("bar", ()) },
+            Err(Error::FunctionNotFound("bar".into()))
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @foo() {
+                    return
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        assert!(ExecutionEngine::try_new(&module, 2, &[], false).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_without_lowering() {
+        let context = create_test_context();
+
+        let module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @foo() {
+                    return
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        ExecutionEngine::new(&module, 2, &[], false);
+    }
+
+    #[test]
+    fn try_new_without_lowering() {
+        let context = create_test_context();
+
+        let module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @foo() {
+                    return
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let Err(Error::CreateExecutionEngine(message)) =
+            ExecutionEngine::try_new(&module, 2, &[], false)
+        else {
+            panic!("execution engine creation must fail");
+        };
+
+        assert!(message.contains("func.func"));
+    }
+
+    #[test]
+    fn register_function() {
+        extern "C" fn increment(value: i64) -> i64 {
+            value + 1
+        }
+
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func private @increment(i64) -> i64
+
+                func.func @foo(%arg0 : i64) -> i64 attributes { llvm.emit_c_interface } {
+                    %0 = call @increment(%arg0) : (i64) -> i64
+                    return %0 : i64
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        let engine = ExecutionEngine::new(&module, 2, &[], false);
+        let integer_type = IntegerType::new(&context, 64).into();
+
+        assert_eq!(
+            engine.register_function(
+                "increment",
+                increment as extern "C" fn(i64) -> i64,
+                Some(FunctionType::new(
+                    &context,
+                    &[integer_type],
+                    &[integer_type]
+                )),
+            ),
+            Ok(())
+        );
+        assert_eq!(unsafe { engine.invoke::<_, i64>("foo", 41i64) }, Ok(42));
+    }
+
+    #[test]
+    fn register_function_with_invalid_type() {
+        extern "C" fn increment(value: i64) -> i64 {
+            value + 1
+        }
+
+        let context = create_test_context();
+        let engine = ExecutionEngine::new(&Module::new(Location::unknown(&context)), 2, &[], false);
+        let integer_type = IntegerType::new(&context, 64).into();
+
+        assert_eq!(
+            engine.register_function(
+                "increment",
+                increment as extern "C" fn(i64) -> i64,
+                Some(FunctionType::new(
+                    &context,
+                    &[integer_type, integer_type],
+                    &[integer_type]
+                )),
+            ),
+            Err(Error::FunctionTypeMismatch {
+                name: "increment".into(),
+                r#type: "(i64, i64) -> i64".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn register_function_with_invalid_element_type() {
+        extern "C" fn increment(value: i64) -> i64 {
+            value + 1
+        }
+
+        let context = create_test_context();
+        let engine = ExecutionEngine::new(&Module::new(Location::unknown(&context)), 2, &[], false);
+        let integer_type = IntegerType::new(&context, 64).into();
+
+        assert_eq!(
+            engine.register_function(
+                "increment",
+                increment as extern "C" fn(i64) -> i64,
+                Some(FunctionType::new(
+                    &context,
+                    &[IntegerType::new(&context, 32).into()],
+                    &[integer_type]
+                )),
+            ),
+            Err(Error::FunctionTypeMismatch {
+                name: "increment".into(),
+                r#type: "(i32) -> i64".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn register_closure() {
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func private @add_offset(!llvm.ptr, i64) -> i64
+
+                func.func @foo(%arg0 : !llvm.ptr, %arg1 : i64) -> i64 attributes { llvm.emit_c_interface } {
+                    %0 = call @add_offset(%arg0, %arg1) : (!llvm.ptr, i64) -> i64
+                    return %0 : i64
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        let engine = ExecutionEngine::new(&module, 2, &[], false);
+        let offset = 42;
+
+        let data = engine
+            .register_closure("add_offset", move |value: i64| value + offset, None)
+            .unwrap();
+
+        assert_eq!(
+            unsafe { engine.invoke::<_, i64>("foo", (data, 1i64)) },
+            Ok(43)
+        );
+    }
+
     #[test]
     fn dump_to_object_file() {
         let context = create_test_context();
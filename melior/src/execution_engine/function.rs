@@ -0,0 +1,173 @@
+use super::{JitResult, JitType};
+use crate::ir::{r#type::FunctionType, Type};
+use std::{ffi::c_void, mem::size_of};
+
+/// A function callable from JIT-compiled functions.
+///
+/// It is implemented for `extern "C"` function pointers whose arguments and
+/// results are FFI-safe.
+pub trait JitFunction: Copy {
+    /// Returns the number of arguments.
+    fn argument_count() -> usize;
+
+    /// Returns the number of results.
+    fn result_count() -> usize;
+
+    /// Returns `true` if a function type matches the function.
+    fn matches(r#type: FunctionType) -> bool;
+
+    /// Converts a function into a pointer.
+    fn to_pointer(self) -> *mut ();
+}
+
+/// A closure callable from JIT-compiled functions through a trampoline.
+///
+/// A trampoline takes a user data pointer to a closure as its first argument
+/// followed by arguments of the closure.
+pub trait JitClosure<A, R> {
+    /// Returns the number of arguments of the closure.
+    fn argument_count() -> usize;
+
+    /// Returns the number of results of the closure.
+    fn result_count() -> usize;
+
+    /// Returns `true` if a function type matches the trampoline.
+    fn matches(r#type: FunctionType) -> bool;
+
+    /// Returns a pointer to a trampoline function.
+    fn trampoline() -> *mut ();
+}
+
+fn result_count<R>() -> usize {
+    if size_of::<R>() == 0 {
+        0
+    } else {
+        1
+    }
+}
+
+fn types<'c>(r#type: FunctionType<'c>) -> (Vec<Type<'c>>, Vec<Type<'c>>) {
+    (
+        (0..r#type.input_count())
+            .map(|index| r#type.input(index).expect("valid input index"))
+            .collect(),
+        (0..r#type.result_count())
+            .map(|index| r#type.result(index).expect("valid result index"))
+            .collect(),
+    )
+}
+
+macro_rules! impl_function {
+    ($count:literal $(, $name:ident)*) => {
+        impl<$($name: JitType,)* R: JitResult> JitFunction for extern "C" fn($($name),*) -> R {
+            fn argument_count() -> usize {
+                $count
+            }
+
+            fn result_count() -> usize {
+                result_count::<R>()
+            }
+
+            #[allow(unused_mut)]
+            fn matches(r#type: FunctionType) -> bool {
+                let (inputs, results) = types(r#type);
+                let mut inputs = inputs.into_iter();
+
+                inputs.len() == $count
+                    $(&& $name::matches(inputs.next().expect("input")))*
+                    && R::matches(&results)
+            }
+
+            fn to_pointer(self) -> *mut () {
+                self as *mut ()
+            }
+        }
+
+        impl<F: Fn($($name),*) -> R, $($name: JitType,)* R: JitResult> JitClosure<($($name,)*), R> for F {
+            fn argument_count() -> usize {
+                $count
+            }
+
+            fn result_count() -> usize {
+                result_count::<R>()
+            }
+
+            fn matches(r#type: FunctionType) -> bool {
+                let (inputs, results) = types(r#type);
+                let mut inputs = inputs.into_iter();
+
+                inputs.len() == $count + 1
+                    && <*const c_void as JitType>::matches(inputs.next().expect("user data"))
+                    $(&& $name::matches(inputs.next().expect("input")))*
+                    && R::matches(&results)
+            }
+
+            fn trampoline() -> *mut () {
+                #[allow(non_snake_case)]
+                extern "C" fn trampoline<F: Fn($($name),*) -> R, $($name,)* R>(
+                    data: *const c_void,
+                    $($name: $name,)*
+                ) -> R {
+                    unsafe { (*(data as *const F))($($name),*) }
+                }
+
+                trampoline::<F, $($name,)* R> as *mut ()
+            }
+        }
+    };
+}
+
+impl_function!(0);
+impl_function!(1, A);
+impl_function!(2, A, B);
+impl_function!(3, A, B, C);
+impl_function!(4, A, B, C, D);
+impl_function!(5, A, B, C, D, E);
+impl_function!(6, A, B, C, D, E, F0);
+impl_function!(7, A, B, C, D, E, F0, G);
+impl_function!(8, A, B, C, D, E, F0, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn add(x: i64, y: i64) -> i64 {
+        x + y
+    }
+
+    extern "C" fn log(_: i32) {}
+
+    #[test]
+    fn function_arity() {
+        assert_eq!(<extern "C" fn(i64, i64) -> i64>::argument_count(), 2);
+        assert_eq!(<extern "C" fn(i64, i64) -> i64>::result_count(), 1);
+        assert_eq!(<extern "C" fn(i32)>::argument_count(), 1);
+        assert_eq!(<extern "C" fn(i32)>::result_count(), 0);
+    }
+
+    #[test]
+    fn function_pointer() {
+        let function: extern "C" fn(i64, i64) -> i64 = add;
+
+        assert_eq!(function.to_pointer(), add as *mut ());
+        assert!(!(log as extern "C" fn(i32)).to_pointer().is_null());
+    }
+
+    #[test]
+    fn call_trampoline() {
+        fn trampoline<F: JitClosure<(i64,), i64>>(_: &F) -> *mut () {
+            F::trampoline()
+        }
+
+        let offset = 42;
+        let closure = |x: i64| x + offset;
+
+        let function = unsafe {
+            std::mem::transmute::<*mut (), extern "C" fn(*const c_void, i64) -> i64>(trampoline(
+                &closure,
+            ))
+        };
+
+        assert_eq!(function(&closure as *const _ as *const c_void, 1), 43);
+    }
+}
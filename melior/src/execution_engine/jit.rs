@@ -0,0 +1,336 @@
+use crate::ir::{
+    r#type::{IntegerType, TypeLike},
+    Type,
+};
+use std::alloc::Layout;
+
+/// A type of arguments passed to JIT-compiled functions.
+///
+/// # Safety
+///
+/// Implementations must push pointers to values whose memory layouts match
+/// the ones of the corresponding arguments in JIT-compiled functions.
+pub unsafe trait JitArgument {
+    /// Pushes pointers to values in the packed calling convention.
+    fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>);
+}
+
+/// A type of results returned from JIT-compiled functions.
+///
+/// Multiple results are returned as a C-compatible struct.
+///
+/// # Safety
+///
+/// Implementations must have the same layouts as the corresponding results
+/// of JIT-compiled functions.
+pub unsafe trait JitReturn: Sized {
+    /// Returns a memory layout of results.
+    fn layout() -> Layout;
+
+    /// Reads results from memory.
+    ///
+    /// # Safety
+    ///
+    /// A pointer must point to valid results.
+    unsafe fn read(pointer: *const u8) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($type:ty),* $(,)?) => {
+        $(
+            unsafe impl JitArgument for $type {
+                fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+                    pointers.push(self as *mut Self as *mut ());
+                }
+            }
+
+            unsafe impl JitReturn for $type {
+                fn layout() -> Layout {
+                    Layout::new::<Self>()
+                }
+
+                unsafe fn read(pointer: *const u8) -> Self {
+                    (pointer as *const Self).read()
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+unsafe impl<T> JitArgument for *const T {
+    fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+        pointers.push(self as *mut Self as *mut ());
+    }
+}
+
+unsafe impl<T> JitReturn for *const T {
+    fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    unsafe fn read(pointer: *const u8) -> Self {
+        (pointer as *const Self).read()
+    }
+}
+
+unsafe impl<T> JitArgument for *mut T {
+    fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+        pointers.push(self as *mut Self as *mut ());
+    }
+}
+
+unsafe impl<T> JitReturn for *mut T {
+    fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    unsafe fn read(pointer: *const u8) -> Self {
+        (pointer as *const Self).read()
+    }
+}
+
+/// A FFI-safe type of arguments and results of functions called from
+/// JIT-compiled functions.
+///
+/// It is implemented for scalar and pointer types.
+///
+/// # Safety
+///
+/// Implementations must be FFI-safe and have the same layouts as values of
+/// matching types in JIT-compiled functions.
+pub unsafe trait JitType: Copy {
+    /// Returns `true` if a type in JIT-compiled functions matches the type.
+    fn matches(r#type: Type) -> bool;
+}
+
+/// A type of results of functions called from JIT-compiled functions.
+///
+/// It is implemented for `()` and [`JitType`]s.
+///
+/// # Safety
+///
+/// Implementations must be FFI-safe.
+pub unsafe trait JitResult {
+    /// Returns `true` if result types in JIT-compiled functions match the
+    /// type.
+    fn matches(types: &[Type]) -> bool;
+}
+
+unsafe impl JitResult for () {
+    fn matches(types: &[Type]) -> bool {
+        types.is_empty()
+    }
+}
+
+macro_rules! impl_type {
+    (<$parameter:ident> $type:ty, |$argument:ident| $matches:expr) => {
+        impl_type!(@impl [$parameter] $type, |$argument| $matches);
+    };
+    ($type:ty, |$argument:ident| $matches:expr) => {
+        impl_type!(@impl [] $type, |$argument| $matches);
+    };
+    (@impl [$($parameter:ident)?] $type:ty, |$argument:ident| $matches:expr) => {
+        unsafe impl$(<$parameter>)? JitType for $type {
+            fn matches($argument: Type) -> bool {
+                $matches
+            }
+        }
+
+        unsafe impl$(<$parameter>)? JitResult for $type {
+            fn matches(types: &[Type]) -> bool {
+                matches!(types, [r#type] if <Self as JitType>::matches(*r#type))
+            }
+        }
+    };
+}
+
+macro_rules! impl_integer_type {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl_type!($type, |r#type| is_integer(r#type, <$type>::BITS));
+        )*
+    };
+}
+
+impl_type!(bool, |r#type| is_integer(r#type, 1));
+impl_integer_type!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_type!(f32, |r#type| r#type.is_f32());
+impl_type!(f64, |r#type| r#type.is_f64());
+impl_type!(<T> *const T, |r#type| r#type.is_llvm_pointer_type());
+impl_type!(<T> *mut T, |r#type| r#type.is_llvm_pointer_type());
+
+fn is_integer(r#type: Type, width: u32) -> bool {
+    (r#type.is_index() && width == usize::BITS)
+        || IntegerType::try_from(r#type).is_ok_and(|r#type| r#type.width() == width)
+}
+
+unsafe impl JitArgument for () {
+    fn push_pointers(&mut self, _: &mut Vec<*mut ()>) {}
+}
+
+unsafe impl JitReturn for () {
+    fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    unsafe fn read(_: *const u8) -> Self {}
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident),*) => {
+        unsafe impl<$($name: JitArgument),*> JitArgument for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+                let ($($name,)*) = self;
+
+                $($name.push_pointers(pointers);)*
+            }
+        }
+
+        unsafe impl<$($name: JitReturn),*> JitReturn for ($($name,)*) {
+            fn layout() -> Layout {
+                struct_layout(&[$($name::layout()),*]).0
+            }
+
+            unsafe fn read(pointer: *const u8) -> Self {
+                let mut offsets = struct_layout(&[$($name::layout()),*]).1.into_iter();
+
+                ($($name::read(pointer.add(offsets.next().expect("field offset"))),)*)
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+impl_tuple!(A, B, C, D, E, F, G);
+impl_tuple!(A, B, C, D, E, F, G, H);
+
+// Returns a layout of a C-compatible struct and offsets of its fields.
+fn struct_layout(fields: &[Layout]) -> (Layout, Vec<usize>) {
+    let mut layout = Layout::new::<()>();
+    let mut offsets = vec![];
+
+    for field in fields {
+        let (next, offset) = layout.extend(*field).expect("valid struct layout");
+
+        layout = next;
+        offsets.push(offset);
+    }
+
+    (layout.pad_to_align(), offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dialect::llvm, test::create_test_context};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn push_scalar_pointers() {
+        let mut value = 42i64;
+        let mut pointers = vec![];
+
+        value.push_pointers(&mut pointers);
+
+        assert_eq!(pointers, [&mut value as *mut i64 as *mut ()]);
+    }
+
+    #[test]
+    fn push_tuple_pointers() {
+        let mut values = (1i32, 2.0f64, true);
+        let mut pointers = vec![];
+
+        values.push_pointers(&mut pointers);
+
+        assert_eq!(
+            pointers,
+            [
+                &mut values.0 as *mut i32 as *mut (),
+                &mut values.1 as *mut f64 as *mut (),
+                &mut values.2 as *mut bool as *mut (),
+            ]
+        );
+    }
+
+    #[test]
+    fn push_unit_pointers() {
+        let mut pointers = vec![];
+
+        ().push_pointers(&mut pointers);
+
+        assert!(pointers.is_empty());
+    }
+
+    #[test]
+    fn tuple_layout() {
+        #[repr(C)]
+        struct Results {
+            _0: i8,
+            _1: i64,
+            _2: bool,
+        }
+
+        assert_eq!(<(i8, i64, bool)>::layout(), Layout::new::<Results>());
+    }
+
+    #[test]
+    fn read_tuple() {
+        #[repr(C)]
+        struct Results {
+            _0: i8,
+            _1: i64,
+            _2: bool,
+        }
+
+        let results = Results {
+            _0: 1,
+            _1: 2,
+            _2: true,
+        };
+
+        assert_eq!(
+            unsafe { <(i8, i64, bool)>::read(&results as *const Results as *const u8) },
+            (1, 2, true)
+        );
+    }
+
+    #[test]
+    fn match_types() {
+        let context = create_test_context();
+
+        assert!(<i64 as JitType>::matches(
+            IntegerType::new(&context, 64).into()
+        ));
+        assert!(!<i64 as JitType>::matches(
+            IntegerType::new(&context, 32).into()
+        ));
+        assert!(<bool as JitType>::matches(
+            IntegerType::new(&context, 1).into()
+        ));
+        assert!(<usize as JitType>::matches(Type::index(&context)));
+        assert!(<f64 as JitType>::matches(Type::float64(&context)));
+        assert!(!<f32 as JitType>::matches(Type::float64(&context)));
+        assert!(<*mut u8 as JitType>::matches(llvm::r#type::pointer(
+            &context, 0
+        )));
+    }
+
+    #[test]
+    fn match_result_types() {
+        let context = create_test_context();
+        let r#type = IntegerType::new(&context, 32).into();
+
+        assert!(<()>::matches(&[]));
+        assert!(<i32 as JitResult>::matches(&[r#type]));
+        assert!(!<i32 as JitResult>::matches(&[]));
+        assert!(!<i32 as JitResult>::matches(&[r#type, r#type]));
+    }
+}
@@ -0,0 +1,292 @@
+use super::{JitArgument, JitReturn};
+use std::{alloc::Layout, ffi::c_void, marker::PhantomData};
+
+/// A strided memref descriptor.
+///
+/// It has the same layout as a memref descriptor of a ranked memref type in
+/// the `llvm` dialect, and can be passed to and returned from JIT-compiled
+/// functions. It mutably borrows a buffer it points to, so it is neither
+/// `Clone` nor `Copy`.
+///
+/// Descriptors returned from JIT-compiled functions have the `'static`
+/// lifetime as they are not tied to any Rust buffer. They may alias buffers of
+/// arguments or point to buffers allocated by JIT-compiled code.
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct StridedMemRef<'a, T, const RANK: usize> {
+    /// A pointer to an allocated buffer.
+    pub allocated: *mut T,
+    /// A pointer to an aligned buffer.
+    pub aligned: *mut T,
+    /// An offset of the first element in the aligned buffer.
+    pub offset: i64,
+    /// Dimension sizes.
+    pub sizes: [i64; RANK],
+    /// Dimension strides.
+    pub strides: [i64; RANK],
+    _buffer: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T, const RANK: usize> StridedMemRef<'a, T, RANK> {
+    /// Creates a memref descriptor of a row-major buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements in the buffer does not match the
+    /// dimension sizes.
+    pub fn new(buffer: &'a mut [T], sizes: [i64; RANK]) -> Self {
+        assert_eq!(
+            buffer.len() as i64,
+            sizes.iter().product::<i64>(),
+            "buffer length must match memref sizes"
+        );
+
+        let mut strides = [1; RANK];
+
+        for index in (0..RANK.saturating_sub(1)).rev() {
+            strides[index] = strides[index + 1] * sizes[index + 1];
+        }
+
+        Self {
+            allocated: buffer.as_mut_ptr(),
+            aligned: buffer.as_mut_ptr(),
+            offset: 0,
+            sizes,
+            strides,
+            _buffer: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.sizes.iter().product::<i64>() as usize
+    }
+
+    /// Returns `true` if a memref has no element.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads an element at indices.
+    ///
+    /// # Safety
+    ///
+    /// A descriptor must point to a valid buffer and indices must be in
+    /// bounds.
+    pub unsafe fn get(&self, indices: [i64; RANK]) -> T
+    where
+        T: Copy,
+    {
+        let offset = self.offset
+            + indices
+                .iter()
+                .zip(self.strides)
+                .map(|(index, stride)| index * stride)
+                .sum::<i64>();
+
+        self.aligned.offset(offset as isize).read()
+    }
+
+    /// Reads all elements in row-major order.
+    ///
+    /// # Safety
+    ///
+    /// A descriptor must point to a valid buffer.
+    pub unsafe fn to_vec(&self) -> Vec<T>
+    where
+        T: Copy,
+    {
+        let mut elements = Vec::with_capacity(self.len());
+        let mut indices = [0; RANK];
+
+        for _ in 0..self.len() {
+            elements.push(self.get(indices));
+
+            for dimension in (0..RANK).rev() {
+                indices[dimension] += 1;
+
+                if indices[dimension] < self.sizes[dimension] {
+                    break;
+                }
+
+                indices[dimension] = 0;
+            }
+        }
+
+        elements
+    }
+}
+
+unsafe impl<T, const RANK: usize> JitArgument for StridedMemRef<'_, T, RANK> {
+    fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+        pointers.push(&mut self.allocated as *mut _ as *mut ());
+        pointers.push(&mut self.aligned as *mut _ as *mut ());
+        pointers.push(&mut self.offset as *mut _ as *mut ());
+
+        for size in &mut self.sizes {
+            pointers.push(size as *mut _ as *mut ());
+        }
+
+        for stride in &mut self.strides {
+            pointers.push(stride as *mut _ as *mut ());
+        }
+    }
+}
+
+unsafe impl<T, const RANK: usize> JitReturn for StridedMemRef<'static, T, RANK> {
+    fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    unsafe fn read(pointer: *const u8) -> Self {
+        (pointer as *const Self).read()
+    }
+}
+
+/// An unranked memref descriptor.
+///
+/// It has the same layout as a memref descriptor of an unranked memref type in
+/// the `llvm` dialect. It mutably borrows a ranked memref descriptor it points
+/// to, so it is neither `Clone` nor `Copy`.
+///
+/// Like [`StridedMemRef`], descriptors returned from JIT-compiled functions
+/// have the `'static` lifetime.
+#[repr(C)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct UnrankedMemRef<'a> {
+    /// A rank.
+    pub rank: i64,
+    /// A pointer to a ranked memref descriptor.
+    pub descriptor: *mut c_void,
+    _descriptor: PhantomData<&'a mut c_void>,
+}
+
+impl<'a> UnrankedMemRef<'a> {
+    /// Creates an unranked memref descriptor from a ranked one.
+    pub fn new<T, const RANK: usize>(descriptor: &'a mut StridedMemRef<'_, T, RANK>) -> Self {
+        Self {
+            rank: RANK as i64,
+            descriptor: descriptor as *mut _ as *mut c_void,
+            _descriptor: PhantomData,
+        }
+    }
+}
+
+unsafe impl JitArgument for UnrankedMemRef<'_> {
+    fn push_pointers(&mut self, pointers: &mut Vec<*mut ()>) {
+        pointers.push(&mut self.rank as *mut _ as *mut ());
+        pointers.push(&mut self.descriptor as *mut _ as *mut ());
+    }
+}
+
+unsafe impl JitReturn for UnrankedMemRef<'static> {
+    fn layout() -> Layout {
+        Layout::new::<Self>()
+    }
+
+    unsafe fn read(pointer: *const u8) -> Self {
+        (pointer as *const Self).read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir::Module, pass, test::create_test_context, ExecutionEngine};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn new() {
+        let mut buffer = [0.0f32; 24];
+        let memref = StridedMemRef::new(&mut buffer, [2, 3, 4]);
+
+        assert_eq!(memref.offset, 0);
+        assert_eq!(memref.sizes, [2, 3, 4]);
+        assert_eq!(memref.strides, [12, 4, 1]);
+        assert_eq!(memref.len(), 24);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_with_invalid_sizes() {
+        StridedMemRef::new(&mut [0i64; 5], [2, 3]);
+    }
+
+    #[test]
+    fn to_vec() {
+        let mut buffer = [1, 2, 3, 4, 5, 6];
+        let memref = StridedMemRef::new(&mut buffer, [2, 3]);
+
+        assert_eq!(unsafe { memref.get([1, 0]) }, 4);
+        assert_eq!(unsafe { memref.to_vec() }, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn to_vec_transposed() {
+        let mut buffer = [1, 2, 3, 4, 5, 6];
+        let mut memref = StridedMemRef::new(&mut buffer, [2, 3]);
+
+        memref.sizes = [3, 2];
+        memref.strides = [1, 3];
+
+        assert_eq!(unsafe { memref.to_vec() }, [1, 4, 2, 5, 3, 6]);
+    }
+
+    #[test]
+    fn unranked() {
+        let mut buffer = [0; 4];
+        let mut memref = StridedMemRef::new(&mut buffer, [2, 2]);
+
+        assert_eq!(UnrankedMemRef::new(&mut memref).rank, 2);
+    }
+
+    #[test]
+    fn invoke() {
+        let context = create_test_context();
+
+        let mut module = Module::parse(
+            &context,
+            r#"
+            module {
+                func.func @double(%arg0 : memref<2x3xi64>) -> memref<2x3xi64> attributes { llvm.emit_c_interface } {
+                    %c0 = arith.constant 0 : index
+                    %c1 = arith.constant 1 : index
+                    %c2 = arith.constant 2 : index
+                    %c3 = arith.constant 3 : index
+                    scf.for %i = %c0 to %c2 step %c1 {
+                        scf.for %j = %c0 to %c3 step %c1 {
+                            %0 = memref.load %arg0[%i, %j] : memref<2x3xi64>
+                            %1 = arith.addi %0, %0 : i64
+                            memref.store %1, %arg0[%i, %j] : memref<2x3xi64>
+                        }
+                    }
+                    return %arg0 : memref<2x3xi64>
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pass_manager = pass::PassManager::new(&context);
+        pass_manager.add_pass(pass::conversion::create_scf_to_control_flow());
+        pass_manager.add_pass(pass::conversion::create_to_llvm());
+        pass_manager.add_pass(pass::conversion::create_reconcile_unrealized_casts());
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+
+        let engine = ExecutionEngine::new(&module, 2, &[], false);
+        let mut buffer = [1i64, 2, 3, 4, 5, 6];
+
+        let result = unsafe {
+            engine.invoke::<_, StridedMemRef<i64, 2>>(
+                "double",
+                StridedMemRef::new(&mut buffer, [2, 3]),
+            )
+        }
+        .unwrap();
+
+        assert_eq!(result.sizes, [2, 3]);
+        assert_eq!(unsafe { result.to_vec() }, [2, 4, 6, 8, 10, 12]);
+        assert_eq!(buffer, [2, 4, 6, 8, 10, 12]);
+    }
+}
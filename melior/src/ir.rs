@@ -1,25 +1,35 @@
 //! IR objects and builders.
 
+mod affine_expr;
 mod affine_map;
 pub mod attribute;
 pub mod block;
+mod bytecode_writer_config;
 mod identifier;
+mod integer_set;
+mod ir_mapping;
 mod location;
 mod module;
 pub mod operation;
 mod region;
+mod symbol_table;
 pub mod r#type;
 mod value;
 
 pub use self::{
+    affine_expr::{AffineExpr, AffineExprKind},
     affine_map::AffineMap,
     attribute::{Attribute, AttributeLike},
     block::{Block, BlockLike, BlockRef},
+    bytecode_writer_config::BytecodeWriterConfig,
     identifier::Identifier,
+    integer_set::IntegerSet,
+    ir_mapping::IrMapping,
     location::Location,
     module::Module,
     operation::{Operation, OperationRef},
     r#type::{ShapedTypeLike, Type, TypeLike},
     region::{Region, RegionLike, RegionRef},
-    value::{Value, ValueLike},
+    symbol_table::SymbolTable,
+    value::{OpOperand, Value, ValueLike},
 };
@@ -0,0 +1,394 @@
+use super::AffineMap;
+use crate::{
+    context::{Context, ContextRef},
+    utility::print_callback,
+};
+use mlir_sys::{
+    mlirAffineAddExprGet, mlirAffineBinaryOpExprGetLHS, mlirAffineBinaryOpExprGetRHS,
+    mlirAffineCeilDivExprGet, mlirAffineConstantExprGet, mlirAffineConstantExprGetValue,
+    mlirAffineDimExprGet, mlirAffineDimExprGetPosition, mlirAffineExprCompose, mlirAffineExprDump,
+    mlirAffineExprEqual, mlirAffineExprGetContext, mlirAffineExprGetLargestKnownDivisor,
+    mlirAffineExprIsAAdd, mlirAffineExprIsABinary, mlirAffineExprIsACeilDiv,
+    mlirAffineExprIsAConstant, mlirAffineExprIsADim, mlirAffineExprIsAFloorDiv,
+    mlirAffineExprIsAMod, mlirAffineExprIsAMul, mlirAffineExprIsASymbol,
+    mlirAffineExprIsFunctionOfDim, mlirAffineExprIsMultipleOf, mlirAffineExprIsPureAffine,
+    mlirAffineExprIsSymbolicOrConstant, mlirAffineExprPrint, mlirAffineFloorDivExprGet,
+    mlirAffineModExprGet, mlirAffineMulExprGet, mlirAffineSymbolExprGet,
+    mlirAffineSymbolExprGetPosition, mlirSimplifyAffineExpr, MlirAffineExpr,
+};
+use std::{
+    ffi::c_void,
+    fmt::{self, Debug, Display, Formatter},
+    marker::PhantomData,
+    ops::{Add, Mul, Rem},
+};
+
+/// An affine expression kind.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum AffineExprKind {
+    Add,
+    CeilDiv,
+    Constant,
+    Dim,
+    FloorDiv,
+    Mod,
+    Mul,
+    Symbol,
+}
+
+/// An affine expression.
+#[derive(Clone, Copy)]
+pub struct AffineExpr<'c> {
+    raw: MlirAffineExpr,
+    _context: PhantomData<&'c Context>,
+}
+
+impl<'c> AffineExpr<'c> {
+    /// Creates a dimension expression.
+    pub fn dim(context: &'c Context, position: usize) -> Self {
+        unsafe { Self::from_raw(mlirAffineDimExprGet(context.to_raw(), position as isize)) }
+    }
+
+    /// Creates a symbol expression.
+    pub fn symbol(context: &'c Context, position: usize) -> Self {
+        unsafe { Self::from_raw(mlirAffineSymbolExprGet(context.to_raw(), position as isize)) }
+    }
+
+    /// Creates a constant expression.
+    pub fn constant(context: &'c Context, value: i64) -> Self {
+        unsafe { Self::from_raw(mlirAffineConstantExprGet(context.to_raw(), value)) }
+    }
+
+    /// Creates a floor division expression.
+    pub fn floor_div(self, other: Self) -> Self {
+        unsafe { Self::from_raw(mlirAffineFloorDivExprGet(self.raw, other.raw)) }
+    }
+
+    /// Creates a ceil division expression.
+    pub fn ceil_div(self, other: Self) -> Self {
+        unsafe { Self::from_raw(mlirAffineCeilDivExprGet(self.raw, other.raw)) }
+    }
+
+    /// Creates a modulo expression.
+    pub fn r#mod(self, other: Self) -> Self {
+        unsafe { Self::from_raw(mlirAffineModExprGet(self.raw, other.raw)) }
+    }
+
+    /// Returns a context.
+    pub fn context(&self) -> ContextRef<'c> {
+        unsafe { ContextRef::from_raw(mlirAffineExprGetContext(self.raw)) }
+    }
+
+    /// Returns a kind.
+    pub fn kind(&self) -> AffineExprKind {
+        unsafe {
+            if mlirAffineExprIsAAdd(self.raw) {
+                AffineExprKind::Add
+            } else if mlirAffineExprIsAMul(self.raw) {
+                AffineExprKind::Mul
+            } else if mlirAffineExprIsAMod(self.raw) {
+                AffineExprKind::Mod
+            } else if mlirAffineExprIsAFloorDiv(self.raw) {
+                AffineExprKind::FloorDiv
+            } else if mlirAffineExprIsACeilDiv(self.raw) {
+                AffineExprKind::CeilDiv
+            } else if mlirAffineExprIsAConstant(self.raw) {
+                AffineExprKind::Constant
+            } else if mlirAffineExprIsADim(self.raw) {
+                AffineExprKind::Dim
+            } else if mlirAffineExprIsASymbol(self.raw) {
+                AffineExprKind::Symbol
+            } else {
+                unreachable!("unknown affine expression kind")
+            }
+        }
+    }
+
+    /// Returns a position of a dimension expression.
+    pub fn dim_position(&self) -> Option<usize> {
+        unsafe { mlirAffineExprIsADim(self.raw) }
+            .then(|| unsafe { mlirAffineDimExprGetPosition(self.raw) } as usize)
+    }
+
+    /// Returns a position of a symbol expression.
+    pub fn symbol_position(&self) -> Option<usize> {
+        unsafe { mlirAffineExprIsASymbol(self.raw) }
+            .then(|| unsafe { mlirAffineSymbolExprGetPosition(self.raw) } as usize)
+    }
+
+    /// Returns a value of a constant expression.
+    pub fn constant_value(&self) -> Option<i64> {
+        unsafe { mlirAffineExprIsAConstant(self.raw) }
+            .then(|| unsafe { mlirAffineConstantExprGetValue(self.raw) })
+    }
+
+    /// Returns a left-hand side operand of a binary expression.
+    pub fn lhs(&self) -> Option<Self> {
+        self.is_binary()
+            .then(|| unsafe { Self::from_raw(mlirAffineBinaryOpExprGetLHS(self.raw)) })
+    }
+
+    /// Returns a right-hand side operand of a binary expression.
+    pub fn rhs(&self) -> Option<Self> {
+        self.is_binary()
+            .then(|| unsafe { Self::from_raw(mlirAffineBinaryOpExprGetRHS(self.raw)) })
+    }
+
+    /// Returns `true` if an expression is made up of only symbols and
+    /// constants.
+    pub fn is_symbolic_or_constant(&self) -> bool {
+        unsafe { mlirAffineExprIsSymbolicOrConstant(self.raw) }
+    }
+
+    /// Returns `true` if an expression is a pure affine expression.
+    pub fn is_pure_affine(&self) -> bool {
+        unsafe { mlirAffineExprIsPureAffine(self.raw) }
+    }
+
+    /// Returns the greatest known integral divisor.
+    pub fn largest_known_divisor(&self) -> i64 {
+        unsafe { mlirAffineExprGetLargestKnownDivisor(self.raw) }
+    }
+
+    /// Returns `true` if an expression is a multiple of a factor.
+    pub fn is_multiple_of(&self, factor: i64) -> bool {
+        unsafe { mlirAffineExprIsMultipleOf(self.raw, factor) }
+    }
+
+    /// Returns `true` if an expression involves a dimension at a position.
+    pub fn is_function_of_dim(&self, position: usize) -> bool {
+        unsafe { mlirAffineExprIsFunctionOfDim(self.raw, position as isize) }
+    }
+
+    /// Composes an expression with an affine map.
+    pub fn compose(&self, map: AffineMap<'c>) -> Self {
+        unsafe { Self::from_raw(mlirAffineExprCompose(self.raw, map.to_raw())) }
+    }
+
+    /// Simplifies an expression.
+    pub fn simplify(&self, dim_count: usize, symbol_count: usize) -> Self {
+        unsafe {
+            Self::from_raw(mlirSimplifyAffineExpr(
+                self.raw,
+                dim_count as u32,
+                symbol_count as u32,
+            ))
+        }
+    }
+
+    /// Dumps an expression.
+    pub fn dump(&self) {
+        unsafe { mlirAffineExprDump(self.raw) }
+    }
+
+    /// Creates an affine expression from a raw object.
+    ///
+    /// # Safety
+    ///
+    /// A raw object must be valid.
+    pub unsafe fn from_raw(raw: MlirAffineExpr) -> Self {
+        Self {
+            raw,
+            _context: Default::default(),
+        }
+    }
+
+    /// Converts an affine expression into a raw object.
+    pub const fn to_raw(self) -> MlirAffineExpr {
+        self.raw
+    }
+
+    fn is_binary(&self) -> bool {
+        unsafe { mlirAffineExprIsABinary(self.raw) }
+    }
+}
+
+impl<'c> Add for AffineExpr<'c> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        unsafe { Self::from_raw(mlirAffineAddExprGet(self.raw, other.raw)) }
+    }
+}
+
+impl<'c> Mul for AffineExpr<'c> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        unsafe { Self::from_raw(mlirAffineMulExprGet(self.raw, other.raw)) }
+    }
+}
+
+impl<'c> Rem for AffineExpr<'c> {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        self.r#mod(other)
+    }
+}
+
+impl PartialEq for AffineExpr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { mlirAffineExprEqual(self.raw, other.raw) }
+    }
+}
+
+impl Eq for AffineExpr<'_> {}
+
+impl Display for AffineExpr<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let mut data = (formatter, Ok(()));
+
+        unsafe {
+            mlirAffineExprPrint(
+                self.raw,
+                Some(print_callback),
+                &mut data as *mut _ as *mut c_void,
+            );
+        }
+
+        data.1
+    }
+}
+
+impl Debug for AffineExpr<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{r#type::MemRefType, Attribute, Type},
+        test::create_test_context,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dim() {
+        let context = create_test_context();
+        let expression = AffineExpr::dim(&context, 1);
+
+        assert_eq!(expression.kind(), AffineExprKind::Dim);
+        assert_eq!(expression.dim_position(), Some(1));
+        assert_eq!(expression.symbol_position(), None);
+        assert_eq!(expression.to_string(), "d1");
+    }
+
+    #[test]
+    fn symbol() {
+        let context = create_test_context();
+        let expression = AffineExpr::symbol(&context, 2);
+
+        assert_eq!(expression.kind(), AffineExprKind::Symbol);
+        assert_eq!(expression.symbol_position(), Some(2));
+        assert_eq!(expression.to_string(), "s2");
+    }
+
+    #[test]
+    fn constant() {
+        let context = create_test_context();
+        let expression = AffineExpr::constant(&context, 42);
+
+        assert_eq!(expression.kind(), AffineExprKind::Constant);
+        assert_eq!(expression.constant_value(), Some(42));
+        assert_eq!(expression.to_string(), "42");
+    }
+
+    #[test]
+    fn binary() {
+        let context = create_test_context();
+        let dim = AffineExpr::dim(&context, 0);
+        let symbol = AffineExpr::symbol(&context, 0);
+        let constant = AffineExpr::constant(&context, 4);
+
+        assert_eq!((dim + symbol).kind(), AffineExprKind::Add);
+        assert_eq!((dim * constant).kind(), AffineExprKind::Mul);
+        assert_eq!((dim % constant).kind(), AffineExprKind::Mod);
+        assert_eq!(dim.floor_div(constant).kind(), AffineExprKind::FloorDiv);
+        assert_eq!(dim.ceil_div(constant).kind(), AffineExprKind::CeilDiv);
+        assert_eq!((dim + symbol).to_string(), "d0 + s0");
+        assert_eq!((dim + symbol).lhs(), Some(dim));
+        assert_eq!((dim + symbol).rhs(), Some(symbol));
+        assert_eq!(dim.lhs(), None);
+    }
+
+    #[test]
+    fn fold_constants() {
+        let context = create_test_context();
+
+        assert_eq!(
+            AffineExpr::constant(&context, 1) + AffineExpr::constant(&context, 2),
+            AffineExpr::constant(&context, 3)
+        );
+    }
+
+    #[test]
+    fn is_pure_affine() {
+        let context = create_test_context();
+        let dim = AffineExpr::dim(&context, 0);
+        let symbol = AffineExpr::symbol(&context, 0);
+
+        assert!((dim * AffineExpr::constant(&context, 2)).is_pure_affine());
+        assert!(!(dim * symbol).is_pure_affine());
+    }
+
+    #[test]
+    fn is_symbolic_or_constant() {
+        let context = create_test_context();
+
+        assert!(AffineExpr::symbol(&context, 0).is_symbolic_or_constant());
+        assert!(!AffineExpr::dim(&context, 0).is_symbolic_or_constant());
+    }
+
+    #[test]
+    fn divisor() {
+        let context = create_test_context();
+        let expression = AffineExpr::dim(&context, 0) * AffineExpr::constant(&context, 6);
+
+        assert_eq!(expression.largest_known_divisor(), 6);
+        assert!(expression.is_multiple_of(3));
+        assert!(!expression.is_multiple_of(4));
+    }
+
+    #[test]
+    fn is_function_of_dim() {
+        let context = create_test_context();
+        let expression = AffineExpr::dim(&context, 1) + AffineExpr::constant(&context, 1);
+
+        assert!(expression.is_function_of_dim(1));
+        assert!(!expression.is_function_of_dim(0));
+    }
+
+    #[test]
+    fn compose() {
+        let context = create_test_context();
+        let map = MemRefType::new(
+            Type::index(&context),
+            &[42],
+            Some(Attribute::parse(&context, "affine_map<(d0) -> (d0 + 1)>").unwrap()),
+            None,
+        )
+        .affine_map();
+
+        assert_eq!(
+            (AffineExpr::dim(&context, 0) * AffineExpr::constant(&context, 2))
+                .compose(map)
+                .to_string(),
+            "d0 * 2 + 2"
+        );
+    }
+
+    #[test]
+    fn simplify() {
+        let context = create_test_context();
+        let dim = AffineExpr::dim(&context, 0);
+
+        assert_eq!(
+            (dim + dim).simplify(1, 0).to_string(),
+            (dim * AffineExpr::constant(&context, 2)).to_string()
+        );
+    }
+}
@@ -1,10 +1,19 @@
+use super::AffineExpr;
 use crate::{
     context::{Context, ContextRef},
     utility::print_callback,
+    Error,
 };
 use mlir_sys::{
-    mlirAffineMapDump, mlirAffineMapEqual, mlirAffineMapGetContext, mlirAffineMapPrint,
-    MlirAffineMap,
+    mlirAffineMapCompressUnusedSymbols, mlirAffineMapConstantGet, mlirAffineMapDump,
+    mlirAffineMapEmptyGet, mlirAffineMapEqual, mlirAffineMapGet, mlirAffineMapGetContext,
+    mlirAffineMapGetMajorSubMap, mlirAffineMapGetMinorSubMap, mlirAffineMapGetNumDims,
+    mlirAffineMapGetNumInputs, mlirAffineMapGetNumResults, mlirAffineMapGetNumSymbols,
+    mlirAffineMapGetResult, mlirAffineMapGetSingleConstantResult, mlirAffineMapGetSubMap,
+    mlirAffineMapIsEmpty, mlirAffineMapIsIdentity, mlirAffineMapIsMinorIdentity,
+    mlirAffineMapIsPermutation, mlirAffineMapIsProjectedPermutation, mlirAffineMapIsSingleConstant,
+    mlirAffineMapMinorIdentityGet, mlirAffineMapMultiDimIdentityGet, mlirAffineMapPermutationGet,
+    mlirAffineMapPrint, mlirAffineMapReplace, mlirAffineMapZeroResultGet, MlirAffineMap,
 };
 use std::{
     ffi::c_void,
@@ -20,11 +29,269 @@ pub struct AffineMap<'c> {
 }
 
 impl<'c> AffineMap<'c> {
+    /// Creates an affine map.
+    pub fn new(
+        context: &'c Context,
+        dim_count: usize,
+        symbol_count: usize,
+        results: &[AffineExpr<'c>],
+    ) -> Self {
+        unsafe {
+            Self::from_raw(mlirAffineMapGet(
+                context.to_raw(),
+                dim_count as isize,
+                symbol_count as isize,
+                results.len() as isize,
+                results.as_ptr() as *mut _,
+            ))
+        }
+    }
+
+    /// Creates an empty affine map.
+    pub fn empty(context: &'c Context) -> Self {
+        unsafe { Self::from_raw(mlirAffineMapEmptyGet(context.to_raw())) }
+    }
+
+    /// Creates an affine map with no result.
+    pub fn zero_result(context: &'c Context, dim_count: usize, symbol_count: usize) -> Self {
+        unsafe {
+            Self::from_raw(mlirAffineMapZeroResultGet(
+                context.to_raw(),
+                dim_count as isize,
+                symbol_count as isize,
+            ))
+        }
+    }
+
+    /// Creates an affine map with a single constant result.
+    pub fn constant(context: &'c Context, value: i64) -> Self {
+        unsafe { Self::from_raw(mlirAffineMapConstantGet(context.to_raw(), value)) }
+    }
+
+    /// Creates an identity affine map.
+    pub fn identity(context: &'c Context, dim_count: usize) -> Self {
+        unsafe {
+            Self::from_raw(mlirAffineMapMultiDimIdentityGet(
+                context.to_raw(),
+                dim_count as isize,
+            ))
+        }
+    }
+
+    /// Creates an identity affine map on the most minor dimensions.
+    pub fn minor_identity(context: &'c Context, dim_count: usize, result_count: usize) -> Self {
+        unsafe {
+            Self::from_raw(mlirAffineMapMinorIdentityGet(
+                context.to_raw(),
+                dim_count as isize,
+                result_count as isize,
+            ))
+        }
+    }
+
+    /// Creates a permutation affine map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a permutation is invalid.
+    pub fn permutation(context: &'c Context, permutation: &[usize]) -> Self {
+        let mut sorted = permutation.to_vec();
+        sorted.sort_unstable();
+
+        assert!(
+            sorted.iter().copied().eq(0..permutation.len()),
+            "invalid permutation: {permutation:?}"
+        );
+
+        let permutation = permutation
+            .iter()
+            .map(|&index| index as u32)
+            .collect::<Vec<_>>();
+
+        unsafe {
+            Self::from_raw(mlirAffineMapPermutationGet(
+                context.to_raw(),
+                permutation.len() as isize,
+                permutation.as_ptr() as *mut _,
+            ))
+        }
+    }
+
     /// Returns a context.
     pub fn context(&self) -> ContextRef<'c> {
         unsafe { ContextRef::from_raw(mlirAffineMapGetContext(self.raw)) }
     }
 
+    /// Returns a number of dimensions.
+    pub fn dim_count(&self) -> usize {
+        unsafe { mlirAffineMapGetNumDims(self.raw) as usize }
+    }
+
+    /// Returns a number of symbols.
+    pub fn symbol_count(&self) -> usize {
+        unsafe { mlirAffineMapGetNumSymbols(self.raw) as usize }
+    }
+
+    /// Returns a number of inputs, which is a sum of numbers of dimensions and
+    /// symbols.
+    pub fn input_count(&self) -> usize {
+        unsafe { mlirAffineMapGetNumInputs(self.raw) as usize }
+    }
+
+    /// Returns a number of results.
+    pub fn result_count(&self) -> usize {
+        unsafe { mlirAffineMapGetNumResults(self.raw) as usize }
+    }
+
+    /// Returns a result at a position.
+    pub fn result(&self, index: usize) -> Result<AffineExpr<'c>, Error> {
+        if index < self.result_count() {
+            unsafe {
+                Ok(AffineExpr::from_raw(mlirAffineMapGetResult(
+                    self.raw,
+                    index as isize,
+                )))
+            }
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "affine map result",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
+    /// Returns results.
+    pub fn results(&self) -> impl Iterator<Item = AffineExpr<'c>> + '_ {
+        (0..self.result_count()).map(|index| unsafe {
+            AffineExpr::from_raw(mlirAffineMapGetResult(self.raw, index as isize))
+        })
+    }
+
+    /// Returns `true` if an affine map is an identity.
+    pub fn is_identity(&self) -> bool {
+        unsafe { mlirAffineMapIsIdentity(self.raw) }
+    }
+
+    /// Returns `true` if an affine map is a minor identity.
+    pub fn is_minor_identity(&self) -> bool {
+        unsafe { mlirAffineMapIsMinorIdentity(self.raw) }
+    }
+
+    /// Returns `true` if an affine map has no dimension, symbol, or result.
+    pub fn is_empty(&self) -> bool {
+        unsafe { mlirAffineMapIsEmpty(self.raw) }
+    }
+
+    /// Returns `true` if an affine map has a single constant result.
+    pub fn is_single_constant(&self) -> bool {
+        unsafe { mlirAffineMapIsSingleConstant(self.raw) }
+    }
+
+    /// Returns a single constant result.
+    pub fn single_constant_result(&self) -> Option<i64> {
+        self.is_single_constant()
+            .then(|| unsafe { mlirAffineMapGetSingleConstantResult(self.raw) })
+    }
+
+    /// Returns `true` if an affine map is a permutation.
+    pub fn is_permutation(&self) -> bool {
+        unsafe { mlirAffineMapIsPermutation(self.raw) }
+    }
+
+    /// Returns `true` if an affine map is a projected permutation.
+    pub fn is_projected_permutation(&self) -> bool {
+        unsafe { mlirAffineMapIsProjectedPermutation(self.raw) }
+    }
+
+    /// Returns an affine map with results at positions.
+    pub fn sub_map(&self, positions: &[usize]) -> Result<Self, Error> {
+        if let Some(&index) = positions
+            .iter()
+            .find(|&&position| position >= self.result_count())
+        {
+            return Err(Error::PositionOutOfBounds {
+                name: "affine map result",
+                value: self.to_string(),
+                index,
+            });
+        }
+
+        let positions = positions
+            .iter()
+            .map(|&position| position as isize)
+            .collect::<Vec<_>>();
+
+        unsafe {
+            Ok(Self::from_raw(mlirAffineMapGetSubMap(
+                self.raw,
+                positions.len() as isize,
+                positions.as_ptr() as *mut _,
+            )))
+        }
+    }
+
+    /// Returns an affine map with the most major results.
+    ///
+    /// It returns `None` if a result count is zero.
+    pub fn major_sub_map(&self, result_count: usize) -> Option<Self> {
+        (result_count > 0).then(|| unsafe {
+            Self::from_raw(mlirAffineMapGetMajorSubMap(self.raw, result_count as isize))
+        })
+    }
+
+    /// Returns an affine map with the most minor results.
+    ///
+    /// It returns `None` if a result count is zero.
+    pub fn minor_sub_map(&self, result_count: usize) -> Option<Self> {
+        (result_count > 0).then(|| unsafe {
+            Self::from_raw(mlirAffineMapGetMinorSubMap(self.raw, result_count as isize))
+        })
+    }
+
+    /// Replaces an expression with another in results of an affine map.
+    pub fn replace(
+        &self,
+        expression: AffineExpr<'c>,
+        replacement: AffineExpr<'c>,
+        result_dim_count: usize,
+        result_symbol_count: usize,
+    ) -> Self {
+        unsafe {
+            Self::from_raw(mlirAffineMapReplace(
+                self.raw,
+                expression.to_raw(),
+                replacement.to_raw(),
+                result_dim_count as isize,
+                result_symbol_count as isize,
+            ))
+        }
+    }
+
+    /// Drops symbols unused in all affine maps and renumbers the remaining
+    /// ones.
+    pub fn compress_unused_symbols(maps: &[Self]) -> Vec<Self> {
+        unsafe extern "C" fn populate(data: *mut c_void, index: isize, map: MlirAffineMap) {
+            let maps = &mut *(data as *mut Vec<MlirAffineMap>);
+
+            maps[index as usize] = map;
+        }
+
+        let mut raw_maps = maps.iter().map(|map| map.raw).collect::<Vec<_>>();
+        let mut results = raw_maps.clone();
+
+        unsafe {
+            mlirAffineMapCompressUnusedSymbols(
+                raw_maps.as_mut_ptr(),
+                raw_maps.len() as isize,
+                &mut results as *mut _ as *mut c_void,
+                Some(populate),
+            );
+
+            results.into_iter().map(|raw| Self::from_raw(raw)).collect()
+        }
+    }
+
     /// Dumps an affine map.
     pub fn dump(&self) {
         unsafe { mlirAffineMapDump(self.raw) }
@@ -41,6 +308,11 @@ impl<'c> AffineMap<'c> {
             _context: Default::default(),
         }
     }
+
+    /// Converts an affine map into a raw object.
+    pub const fn to_raw(self) -> MlirAffineMap {
+        self.raw
+    }
 }
 
 impl PartialEq for AffineMap<'_> {
@@ -72,3 +344,190 @@ impl Debug for AffineMap<'_> {
         Display::fmt(self, formatter)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::create_test_context;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+        let map = AffineMap::new(
+            &context,
+            2,
+            1,
+            &[
+                AffineExpr::dim(&context, 0) + AffineExpr::symbol(&context, 0),
+                AffineExpr::dim(&context, 1),
+            ],
+        );
+
+        assert_eq!(map.to_string(), "(d0, d1)[s0] -> (d0 + s0, d1)");
+        assert_eq!(map.dim_count(), 2);
+        assert_eq!(map.symbol_count(), 1);
+        assert_eq!(map.input_count(), 3);
+        assert_eq!(map.result_count(), 2);
+    }
+
+    #[test]
+    fn empty() {
+        let context = create_test_context();
+        let map = AffineMap::empty(&context);
+
+        assert!(map.is_empty());
+        assert_eq!(map.to_string(), "() -> ()");
+    }
+
+    #[test]
+    fn zero_result() {
+        let context = create_test_context();
+
+        assert_eq!(
+            AffineMap::zero_result(&context, 2, 1).to_string(),
+            "(d0, d1)[s0] -> ()"
+        );
+    }
+
+    #[test]
+    fn constant() {
+        let context = create_test_context();
+        let map = AffineMap::constant(&context, 42);
+
+        assert!(map.is_single_constant());
+        assert_eq!(map.single_constant_result(), Some(42));
+        assert_eq!(
+            AffineMap::identity(&context, 1).single_constant_result(),
+            None
+        );
+    }
+
+    #[test]
+    fn identity() {
+        let context = create_test_context();
+        let map = AffineMap::identity(&context, 2);
+
+        assert!(map.is_identity());
+        assert!(map.is_permutation());
+        assert_eq!(map.to_string(), "(d0, d1) -> (d0, d1)");
+    }
+
+    #[test]
+    fn minor_identity() {
+        let context = create_test_context();
+        let map = AffineMap::minor_identity(&context, 3, 2);
+
+        assert!(map.is_minor_identity());
+        assert!(!map.is_identity());
+        assert_eq!(map.to_string(), "(d0, d1, d2) -> (d1, d2)");
+    }
+
+    #[test]
+    fn permutation() {
+        let context = create_test_context();
+        let map = AffineMap::permutation(&context, &[1, 2, 0]);
+
+        assert!(map.is_permutation());
+        assert!(map.is_projected_permutation());
+        assert_eq!(map.to_string(), "(d0, d1, d2) -> (d1, d2, d0)");
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_permutation() {
+        AffineMap::permutation(&create_test_context(), &[0, 0]);
+    }
+
+    #[test]
+    fn result() {
+        let context = create_test_context();
+        let map = AffineMap::identity(&context, 2);
+
+        assert_eq!(map.result(1), Ok(AffineExpr::dim(&context, 1)));
+        assert_eq!(
+            map.result(2),
+            Err(Error::PositionOutOfBounds {
+                name: "affine map result",
+                value: map.to_string(),
+                index: 2,
+            })
+        );
+        assert_eq!(
+            map.results().collect::<Vec<_>>(),
+            [AffineExpr::dim(&context, 0), AffineExpr::dim(&context, 1)]
+        );
+    }
+
+    #[test]
+    fn sub_map() {
+        let context = create_test_context();
+        let map = AffineMap::identity(&context, 3);
+
+        assert_eq!(
+            map.sub_map(&[0, 2]).unwrap().to_string(),
+            "(d0, d1, d2) -> (d0, d2)"
+        );
+        assert_eq!(
+            map.major_sub_map(1).unwrap().to_string(),
+            "(d0, d1, d2) -> (d0)"
+        );
+        assert_eq!(
+            map.minor_sub_map(1).unwrap().to_string(),
+            "(d0, d1, d2) -> (d2)"
+        );
+        assert!(map.sub_map(&[0, 2]).unwrap().is_projected_permutation());
+        assert!(!map.sub_map(&[0, 2]).unwrap().is_permutation());
+    }
+
+    #[test]
+    fn sub_map_out_of_bounds() {
+        let context = create_test_context();
+        let map = AffineMap::identity(&context, 3);
+
+        assert_eq!(
+            map.sub_map(&[0, 3]),
+            Err(Error::PositionOutOfBounds {
+                name: "affine map result",
+                value: map.to_string(),
+                index: 3,
+            })
+        );
+        assert_eq!(map.major_sub_map(0), None);
+        assert_eq!(map.minor_sub_map(0), None);
+    }
+
+    #[test]
+    fn replace() {
+        let context = create_test_context();
+        let map = AffineMap::identity(&context, 2);
+
+        assert_eq!(
+            map.replace(
+                AffineExpr::dim(&context, 1),
+                AffineExpr::constant(&context, 0),
+                2,
+                0
+            )
+            .to_string(),
+            "(d0, d1) -> (d0, 0)"
+        );
+    }
+
+    #[test]
+    fn compress_unused_symbols() {
+        let context = create_test_context();
+        let maps = [
+            AffineMap::new(&context, 1, 2, &[AffineExpr::symbol(&context, 1)]),
+            AffineMap::new(&context, 1, 2, &[AffineExpr::dim(&context, 0)]),
+        ];
+
+        assert_eq!(
+            AffineMap::compress_unused_symbols(&maps)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["(d0)[s0] -> (s0)", "(d0)[s0] -> (d0)"]
+        );
+    }
+}
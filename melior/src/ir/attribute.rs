@@ -12,6 +12,7 @@ mod distinct;
 mod flat_symbol_ref;
 mod float;
 mod integer;
+mod integer_set;
 mod string;
 mod r#type;
 
@@ -19,8 +20,8 @@ pub use self::{
     array::ArrayAttribute, attribute_like::AttributeLike, bool::BoolAttribute,
     dense_elements::DenseElementsAttribute, dense_i32_array::DenseI32ArrayAttribute,
     dense_i64_array::DenseI64ArrayAttribute, flat_symbol_ref::FlatSymbolRefAttribute,
-    float::FloatAttribute, integer::IntegerAttribute, r#type::TypeAttribute,
-    string::StringAttribute,
+    float::FloatAttribute, integer::IntegerAttribute, integer_set::IntegerSetAttribute,
+    r#type::TypeAttribute, string::StringAttribute,
 };
 use crate::{context::Context, string_ref::StringRef, utility::print_callback};
 use distinct::DisctinctAttribute;
@@ -134,6 +135,7 @@ from_subtypes!(
     FlatSymbolRefAttribute,
     FloatAttribute,
     IntegerAttribute,
+    IntegerSetAttribute,
     StringAttribute,
     TypeAttribute,
     DisctinctAttribute,
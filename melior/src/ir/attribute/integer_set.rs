@@ -0,0 +1,48 @@
+use super::{Attribute, AttributeLike};
+use crate::{ir::IntegerSet, Error};
+use mlir_sys::{mlirIntegerSetAttrGet, mlirIntegerSetAttrGetValue, MlirAttribute};
+
+/// An integer set attribute.
+#[derive(Clone, Copy)]
+pub struct IntegerSetAttribute<'c> {
+    attribute: Attribute<'c>,
+}
+
+impl<'c> IntegerSetAttribute<'c> {
+    /// Creates an integer set attribute.
+    pub fn new(set: IntegerSet<'c>) -> Self {
+        unsafe { Self::from_raw(mlirIntegerSetAttrGet(set.to_raw())) }
+    }
+
+    /// Returns an integer set value.
+    pub fn value(&self) -> IntegerSet<'c> {
+        unsafe { IntegerSet::from_raw(mlirIntegerSetAttrGetValue(self.to_raw())) }
+    }
+}
+
+attribute_traits!(IntegerSetAttribute, is_integer_set, "integer set");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir::AffineExpr, test::create_test_context};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn value() {
+        let context = create_test_context();
+        let set = IntegerSet::new(&context, 1, 0, &[AffineExpr::dim(&context, 0)], &[false]);
+
+        assert_eq!(IntegerSetAttribute::new(set).value(), set);
+    }
+
+    #[test]
+    fn to_string() {
+        let context = create_test_context();
+
+        assert_eq!(
+            IntegerSetAttribute::new(IntegerSet::empty(&context, 1, 0)).to_string(),
+            "affine_set<(d0) : (1 == 0)>"
+        );
+    }
+}
@@ -4,10 +4,11 @@ mod argument;
 mod block_like;
 
 pub use self::{argument::BlockArgument, block_like::BlockLike};
-use super::{Location, Type, TypeLike, Value};
+use super::{Location, Type, TypeLike, Value, ValueLike};
 use crate::{context::Context, utility::print_callback};
 use mlir_sys::{
-    mlirBlockCreate, mlirBlockDestroy, mlirBlockDetach, mlirBlockEqual, mlirBlockPrint, MlirBlock,
+    mlirBlockAppendOwnedOperation, mlirBlockCreate, mlirBlockDestroy, mlirBlockDetach,
+    mlirBlockEqual, mlirBlockPrint, mlirOperationRemoveFromParent, MlirBlock,
 };
 use std::{
     ffi::c_void,
@@ -139,7 +140,69 @@ pub struct BlockRef<'c, 'a> {
     _reference: PhantomData<&'a Block<'c>>,
 }
 
-impl BlockRef<'_, '_> {
+impl<'c> BlockRef<'c, '_> {
+    /// Erases a block from its parent region and destroys it.
+    ///
+    /// # Safety
+    ///
+    /// No reference to the block, its arguments, or its operations can be used
+    /// after this call, and values defined in the block must have no uses
+    /// outside of it.
+    pub unsafe fn erase(self) {
+        if self.parent_region().is_some() {
+            mlirBlockDetach(self.raw);
+        }
+
+        mlirBlockDestroy(self.raw)
+    }
+
+    /// Merges a block into the end of another block.
+    ///
+    /// Uses of arguments of the block are replaced with given values. Then,
+    /// all operations in the block are moved into the other block, and the
+    /// block is erased.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blocks are the same or the numbers of arguments and
+    /// values are different.
+    ///
+    /// # Safety
+    ///
+    /// No reference to the block or its arguments can be used after this
+    /// call. The block must have no predecessors, and the other block must not
+    /// end with a terminator unless the block is empty. The values must
+    /// dominate the end of the other block.
+    pub unsafe fn merge_into<'b>(
+        self,
+        block: &impl BlockLike<'c, 'b>,
+        arguments: &[Value<'c, '_>],
+    ) {
+        assert_ne!(
+            self.raw.ptr,
+            block.to_raw().ptr,
+            "block cannot be merged into itself"
+        );
+        assert_eq!(
+            self.argument_count(),
+            arguments.len(),
+            "numbers of block arguments and values must match"
+        );
+
+        for (index, value) in arguments.iter().enumerate() {
+            self.argument(index)
+                .expect("valid argument index")
+                .replace_all_uses_with(*value);
+        }
+
+        while let Some(operation) = self.first_operation() {
+            mlirOperationRemoveFromParent(operation.to_raw());
+            mlirBlockAppendOwnedOperation(block.to_raw(), operation.to_raw());
+        }
+
+        self.erase()
+    }
+
     /// Creates a block reference from a raw object.
     ///
     /// # Safety
@@ -205,7 +268,7 @@ mod tests {
     use super::*;
     use crate::{
         ir::{
-            operation::{OperationBuilder, OperationLike},
+            operation::{OperationBuilder, OperationLike, WalkOrder, WalkResult},
             r#type::IntegerType,
             Module, Region, RegionLike, ValueLike,
         },
@@ -412,6 +475,238 @@ mod tests {
         assert_eq!(first_block.next_in_region(), Some(second_block));
     }
 
+    #[test]
+    fn insert_argument() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let block = Block::new(&[(Type::index(&context), location)]);
+        let r#type = IntegerType::new(&context, 64).into();
+
+        let argument = block.insert_argument(0, r#type, location).unwrap();
+
+        assert_eq!(Value::from(block.argument(0).unwrap()), argument);
+        assert_eq!(argument.r#type(), r#type);
+        assert_eq!(block.argument_count(), 2);
+        assert_eq!(
+            block.insert_argument(3, r#type, location),
+            Err(Error::PositionOutOfBounds {
+                name: "block argument",
+                value: block.to_string(),
+                index: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn erase_argument() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let r#type = IntegerType::new(&context, 64).into();
+        let block = Block::new(&[(Type::index(&context), location), (r#type, location)]);
+
+        assert_eq!(block.erase_argument(0), Ok(()));
+        assert_eq!(block.argument_count(), 1);
+        assert_eq!(block.argument(0).unwrap().r#type(), r#type);
+        assert_eq!(
+            block.erase_argument(1),
+            Err(Error::PositionOutOfBounds {
+                name: "block argument",
+                value: block.to_string(),
+                index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn split_at() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let last_block = region.append_block(Block::new(&[]));
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let bar = block.append_operation(OperationBuilder::new("bar", location).build().unwrap());
+        block.append_operation(OperationBuilder::new("baz", location).build().unwrap());
+
+        let new_block = block.split_at(bar).unwrap();
+
+        assert_eq!(block.next_in_region(), Some(new_block));
+        assert_eq!(new_block.next_in_region(), Some(last_block));
+        assert_eq!(block.first_operation(), Some(foo));
+        assert_eq!(foo.next_in_block(), None);
+        assert_eq!(new_block.first_operation(), Some(bar));
+        assert_eq!(bar.block(), Some(new_block));
+        assert_eq!(
+            bar.next_in_block().unwrap().name().as_string_ref().as_str(),
+            Ok("baz")
+        );
+    }
+
+    #[test]
+    fn split_at_operation_in_other_block() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        let foo =
+            other_block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+
+        assert_eq!(
+            block.split_at(foo),
+            Err(Error::OperationNotInBlock(foo.to_string()))
+        );
+        assert_eq!(other_block.first_operation(), Some(foo));
+    }
+
+    #[test]
+    fn split_at_without_region() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+
+        assert_eq!(
+            block.split_at(foo),
+            Err(Error::ParentRegionNotFound(block.to_string()))
+        );
+    }
+
+    #[test]
+    fn erase() {
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        unsafe { block.erase() };
+
+        assert_eq!(region.first_block(), Some(other_block));
+        assert_eq!(other_block.next_in_region(), None);
+    }
+
+    #[test]
+    fn merge_into() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[(r#type, location)]));
+        let other_block = region.append_block(Block::new(&[(r#type, location)]));
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let bar = other_block.append_operation(
+            OperationBuilder::new("bar", location)
+                .add_operands(&[other_block.argument(0).unwrap().into()])
+                .build()
+                .unwrap(),
+        );
+
+        unsafe { other_block.merge_into(&block, &[block.argument(0).unwrap().into()]) };
+
+        assert_eq!(block.next_in_region(), None);
+        assert_eq!(foo.next_in_block(), Some(bar));
+        assert_eq!(bar.operand(0), Ok(block.argument(0).unwrap().into()));
+    }
+
+    #[test]
+    fn operations() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let bar = block.append_operation(OperationBuilder::new("bar", location).build().unwrap());
+
+        assert_eq!(block.operations().collect::<Vec<_>>(), [foo, bar]);
+        assert_eq!(block.operations_rev().collect::<Vec<_>>(), [bar, foo]);
+        assert_eq!(Block::new(&[]).operations().count(), 0);
+    }
+
+    #[test]
+    fn successors_and_predecessors() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+        let last_block = region.append_block(Block::new(&[]));
+
+        block.append_operation(
+            OperationBuilder::new("foo", location)
+                .add_successors(&[&other_block, &last_block])
+                .build()
+                .unwrap(),
+        );
+        other_block.append_operation(
+            OperationBuilder::new("bar", location)
+                .add_successors(&[&last_block])
+                .build()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            block.successors().collect::<Vec<_>>(),
+            [other_block, last_block]
+        );
+        assert_eq!(block.predecessor_count(), 0);
+        assert_eq!(other_block.predecessors().collect::<Vec<_>>(), [block]);
+        assert_eq!(last_block.predecessor_count(), 2);
+        assert_eq!(last_block.successor_count(), 0);
+    }
+
+    #[test]
+    fn walk_mut() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        block.append_operation(OperationBuilder::new("bar", location).build().unwrap());
+        block.append_operation(OperationBuilder::new("baz", location).build().unwrap());
+
+        let mut count = 0;
+
+        assert_eq!(
+            block.walk_mut(WalkOrder::PostOrder, |operation| {
+                count += 1;
+
+                if operation.name().as_string_ref().as_str() == Ok("bar") {
+                    WalkResult::Interrupt
+                } else {
+                    WalkResult::Advance
+                }
+            }),
+            WalkResult::Interrupt
+        );
+        assert_eq!(count, 2);
+
+        assert_eq!(
+            block.walk_mut(WalkOrder::PostOrder, |operation| {
+                unsafe { operation.erase() };
+                WalkResult::Advance
+            }),
+            WalkResult::Advance
+        );
+        assert_eq!(block.first_operation(), None);
+    }
+
     #[test]
     fn detach() {
         let region = Region::new();
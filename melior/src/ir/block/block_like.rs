@@ -1,15 +1,21 @@
 use super::{BlockArgument, BlockRef, TypeLike};
 use crate::{
-    ir::{operation::OperationRefMut, Location, Operation, OperationRef, RegionRef, Type, Value},
+    ir::{
+        operation::{OperationLike, OperationMutLike, OperationRefMut, WalkOrder, WalkResult},
+        Block, Location, Operation, OperationRef, RegionLike, RegionRef, Type, Value,
+    },
     Error,
 };
-use core::fmt::Display;
+use core::{fmt::Display, iter::successors};
 use mlir_sys::{
-    mlirBlockAddArgument, mlirBlockAppendOwnedOperation, mlirBlockGetArgument,
-    mlirBlockGetFirstOperation, mlirBlockGetNextInRegion, mlirBlockGetNumArguments,
-    mlirBlockGetParentOperation, mlirBlockGetParentRegion, mlirBlockGetTerminator,
+    mlirBlockAddArgument, mlirBlockAppendOwnedOperation, mlirBlockEraseArgument,
+    mlirBlockGetArgument, mlirBlockGetFirstOperation, mlirBlockGetNextInRegion,
+    mlirBlockGetNumArguments, mlirBlockGetNumPredecessors, mlirBlockGetNumSuccessors,
+    mlirBlockGetParentOperation, mlirBlockGetParentRegion, mlirBlockGetPredecessor,
+    mlirBlockGetSuccessor, mlirBlockGetTerminator, mlirBlockInsertArgument,
     mlirBlockInsertOwnedOperation, mlirBlockInsertOwnedOperationAfter,
-    mlirBlockInsertOwnedOperationBefore, MlirBlock,
+    mlirBlockInsertOwnedOperationBefore, mlirOperationGetNextInBlock,
+    mlirOperationRemoveFromParent, MlirBlock,
 };
 
 /// A trait for block-like types.
@@ -84,6 +90,48 @@ pub trait BlockLike<'c, 'a>: Display + 'a {
         }
     }
 
+    /// Inserts an argument at a position.
+    fn insert_argument(
+        &self,
+        index: usize,
+        r#type: Type<'c>,
+        location: Location<'c>,
+    ) -> Result<Value<'c, 'a>, Error> {
+        if index <= self.argument_count() {
+            Ok(unsafe {
+                Value::from_raw(mlirBlockInsertArgument(
+                    self.to_raw(),
+                    index as isize,
+                    r#type.to_raw(),
+                    location.to_raw(),
+                ))
+            })
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "block argument",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
+    /// Erases an argument at a position.
+    ///
+    /// The argument must have no use.
+    fn erase_argument(&self, index: usize) -> Result<(), Error> {
+        if index < self.argument_count() {
+            unsafe { mlirBlockEraseArgument(self.to_raw(), index as _) }
+
+            Ok(())
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "block argument",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
     /// Appends an operation.
     fn append_operation(&self, operation: Operation<'c>) -> OperationRef<'c, 'a> {
         unsafe {
@@ -138,8 +186,128 @@ pub trait BlockLike<'c, 'a>: Display + 'a {
         }
     }
 
+    /// Splits a block at an operation.
+    ///
+    /// The operation and all following ones in the block are moved into a new
+    /// block without arguments, which is inserted after the block in its
+    /// parent region.
+    ///
+    /// It returns an error if the operation is not in the block or the block
+    /// is not in a region.
+    fn split_at(&self, operation: OperationRef<'c, 'a>) -> Result<BlockRef<'c, 'a>, Error> {
+        let this = unsafe { BlockRef::from_raw(self.to_raw()) };
+
+        if operation.block() != Some(this) {
+            return Err(Error::OperationNotInBlock(operation.to_string()));
+        }
+
+        let region = self
+            .parent_region()
+            .ok_or_else(|| Error::ParentRegionNotFound(self.to_string()))?;
+        let block = region.insert_block_after(this, Block::new(&[]));
+        let mut current = Some(operation);
+
+        while let Some(operation) = current {
+            current = operation.next_in_block();
+
+            unsafe {
+                mlirOperationRemoveFromParent(operation.to_raw());
+                mlirBlockAppendOwnedOperation(block.to_raw(), operation.to_raw());
+            }
+        }
+
+        Ok(block)
+    }
+
+    /// Returns all operations.
+    fn operations(&self) -> impl Iterator<Item = OperationRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        successors(self.first_operation(), |operation| {
+            operation.next_in_block()
+        })
+    }
+
+    /// Returns all operations in reverse order.
+    ///
+    /// Operations are collected eagerly as the C API does not provide
+    /// backward traversal.
+    fn operations_rev(&self) -> impl Iterator<Item = OperationRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        self.operations().collect::<Vec<_>>().into_iter().rev()
+    }
+
+    /// Returns the number of successors.
+    fn successor_count(&self) -> usize {
+        unsafe { mlirBlockGetNumSuccessors(self.to_raw()) as usize }
+    }
+
+    /// Returns all successors.
+    fn successors(&self) -> impl Iterator<Item = BlockRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        (0..self.successor_count()).map(|index| unsafe {
+            BlockRef::from_raw(mlirBlockGetSuccessor(self.to_raw(), index as isize))
+        })
+    }
+
+    /// Returns the number of predecessors.
+    fn predecessor_count(&self) -> usize {
+        unsafe { mlirBlockGetNumPredecessors(self.to_raw()) as usize }
+    }
+
+    /// Returns all predecessors.
+    fn predecessors(&self) -> impl Iterator<Item = BlockRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        (0..self.predecessor_count()).map(|index| unsafe {
+            BlockRef::from_raw(mlirBlockGetPredecessor(self.to_raw(), index as isize))
+        })
+    }
+
     /// Returns a next block in a region.
     fn next_in_region(&self) -> Option<BlockRef<'c, 'a>> {
         unsafe { BlockRef::from_option_raw(mlirBlockGetNextInRegion(self.to_raw())) }
     }
+
+    /// Walks operations in a block and their nested operations in either pre-
+    /// or post-order with mutable references.
+    ///
+    /// The same rules as [`OperationMutLike::walk_mut`] apply to erasure of
+    /// visited operations, including the ones directly in the block. It
+    /// returns `WalkResult::Interrupt` if the walk is interrupted.
+    fn walk_mut<F>(&self, order: WalkOrder, mut callback: F) -> WalkResult
+    where
+        'c: 'a,
+        F: for<'x, 'y> FnMut(OperationRefMut<'x, 'y>) -> WalkResult,
+    {
+        let mut interrupted = false;
+        let mut current = self.first_operation_mut();
+
+        while let Some(mut operation) = current {
+            // Get the next operation first as the current one can be erased.
+            current = unsafe {
+                OperationRefMut::from_option_raw(mlirOperationGetNextInBlock(operation.to_raw()))
+            };
+
+            operation.walk_mut(order, |operation: OperationRefMut| {
+                let result = callback(operation);
+
+                interrupted |= result == WalkResult::Interrupt;
+
+                result
+            });
+
+            if interrupted {
+                return WalkResult::Interrupt;
+            }
+        }
+
+        WalkResult::Advance
+    }
 }
@@ -0,0 +1,43 @@
+use mlir_sys::{
+    mlirBytecodeWriterConfigCreate, mlirBytecodeWriterConfigDesiredEmitVersion,
+    mlirBytecodeWriterConfigDestroy, MlirBytecodeWriterConfig,
+};
+
+/// A bytecode writer configuration.
+///
+/// A producer string in bytecode is always the default one of MLIR as the C
+/// API does not allow to configure it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BytecodeWriterConfig {
+    /// A bytecode version to emit. If it is `None`, the current version is
+    /// used.
+    pub desired_version: Option<i64>,
+}
+
+impl BytecodeWriterConfig {
+    pub(crate) fn to_raw(&self) -> RawBytecodeWriterConfig {
+        let raw = unsafe { mlirBytecodeWriterConfigCreate() };
+
+        if let Some(version) = self.desired_version {
+            unsafe { mlirBytecodeWriterConfigDesiredEmitVersion(raw, version) }
+        }
+
+        RawBytecodeWriterConfig { raw }
+    }
+}
+
+pub(crate) struct RawBytecodeWriterConfig {
+    raw: MlirBytecodeWriterConfig,
+}
+
+impl RawBytecodeWriterConfig {
+    pub const fn to_raw(&self) -> MlirBytecodeWriterConfig {
+        self.raw
+    }
+}
+
+impl Drop for RawBytecodeWriterConfig {
+    fn drop(&mut self) {
+        unsafe { mlirBytecodeWriterConfigDestroy(self.raw) }
+    }
+}
@@ -0,0 +1,332 @@
+use super::AffineExpr;
+use crate::{
+    context::{Context, ContextRef},
+    utility::print_callback,
+    Error,
+};
+use mlir_sys::{
+    mlirIntegerSetDump, mlirIntegerSetEmptyGet, mlirIntegerSetEqual, mlirIntegerSetGet,
+    mlirIntegerSetGetConstraint, mlirIntegerSetGetContext, mlirIntegerSetGetNumConstraints,
+    mlirIntegerSetGetNumDims, mlirIntegerSetGetNumEqualities, mlirIntegerSetGetNumInequalities,
+    mlirIntegerSetGetNumInputs, mlirIntegerSetGetNumSymbols, mlirIntegerSetIsCanonicalEmpty,
+    mlirIntegerSetIsConstraintEq, mlirIntegerSetPrint, mlirIntegerSetReplaceGet, MlirIntegerSet,
+};
+use std::{
+    ffi::c_void,
+    fmt::{self, Debug, Display, Formatter},
+    marker::PhantomData,
+};
+
+/// An integer set.
+///
+/// An integer set is a conjunction of affine constraints, each of which is
+/// either an equality (`== 0`) or an inequality (`>= 0`).
+#[derive(Clone, Copy)]
+pub struct IntegerSet<'c> {
+    raw: MlirIntegerSet,
+    _context: PhantomData<&'c Context>,
+}
+
+impl<'c> IntegerSet<'c> {
+    /// Creates an integer set.
+    ///
+    /// Each flag in `equalities` tells if a corresponding constraint is an
+    /// equality.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numbers of constraints and flags are different.
+    pub fn new(
+        context: &'c Context,
+        dim_count: usize,
+        symbol_count: usize,
+        constraints: &[AffineExpr<'c>],
+        equalities: &[bool],
+    ) -> Self {
+        assert_eq!(
+            constraints.len(),
+            equalities.len(),
+            "numbers of constraints and equality flags must match"
+        );
+
+        unsafe {
+            Self::from_raw(mlirIntegerSetGet(
+                context.to_raw(),
+                dim_count as isize,
+                symbol_count as isize,
+                constraints.len() as isize,
+                constraints.as_ptr() as *const _,
+                equalities.as_ptr(),
+            ))
+        }
+    }
+
+    /// Creates a canonical empty integer set.
+    pub fn empty(context: &'c Context, dim_count: usize, symbol_count: usize) -> Self {
+        unsafe {
+            Self::from_raw(mlirIntegerSetEmptyGet(
+                context.to_raw(),
+                dim_count as isize,
+                symbol_count as isize,
+            ))
+        }
+    }
+
+    /// Creates an integer set with dimensions and symbols replaced with
+    /// expressions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the numbers of replacements do not match the ones of
+    /// dimensions and symbols.
+    pub fn replace(
+        &self,
+        dim_replacements: &[AffineExpr<'c>],
+        symbol_replacements: &[AffineExpr<'c>],
+        result_dim_count: usize,
+        result_symbol_count: usize,
+    ) -> Self {
+        assert_eq!(dim_replacements.len(), self.dim_count());
+        assert_eq!(symbol_replacements.len(), self.symbol_count());
+
+        unsafe {
+            Self::from_raw(mlirIntegerSetReplaceGet(
+                self.raw,
+                dim_replacements.as_ptr() as *const _,
+                symbol_replacements.as_ptr() as *const _,
+                result_dim_count as isize,
+                result_symbol_count as isize,
+            ))
+        }
+    }
+
+    /// Returns a context.
+    pub fn context(&self) -> ContextRef<'c> {
+        unsafe { ContextRef::from_raw(mlirIntegerSetGetContext(self.raw)) }
+    }
+
+    /// Returns `true` if an integer set is a canonical empty set.
+    pub fn is_canonical_empty(&self) -> bool {
+        unsafe { mlirIntegerSetIsCanonicalEmpty(self.raw) }
+    }
+
+    /// Returns a number of dimensions.
+    pub fn dim_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumDims(self.raw) as usize }
+    }
+
+    /// Returns a number of symbols.
+    pub fn symbol_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumSymbols(self.raw) as usize }
+    }
+
+    /// Returns a number of inputs, which is a sum of numbers of dimensions and
+    /// symbols.
+    pub fn input_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumInputs(self.raw) as usize }
+    }
+
+    /// Returns a number of constraints.
+    pub fn constraint_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumConstraints(self.raw) as usize }
+    }
+
+    /// Returns a number of equality constraints.
+    pub fn equality_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumEqualities(self.raw) as usize }
+    }
+
+    /// Returns a number of inequality constraints.
+    pub fn inequality_count(&self) -> usize {
+        unsafe { mlirIntegerSetGetNumInequalities(self.raw) as usize }
+    }
+
+    /// Returns a constraint at a position and `true` if it is an equality.
+    pub fn constraint(&self, index: usize) -> Result<(AffineExpr<'c>, bool), Error> {
+        if index < self.constraint_count() {
+            Ok(unsafe { self.constraint_unchecked(index) })
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "integer set constraint",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
+    /// Returns constraints with flags of `true` for equalities.
+    pub fn constraints(&self) -> impl Iterator<Item = (AffineExpr<'c>, bool)> + '_ {
+        (0..self.constraint_count()).map(|index| unsafe { self.constraint_unchecked(index) })
+    }
+
+    /// Dumps an integer set.
+    pub fn dump(&self) {
+        unsafe { mlirIntegerSetDump(self.raw) }
+    }
+
+    /// Creates an integer set from a raw object.
+    ///
+    /// # Safety
+    ///
+    /// A raw object must be valid.
+    pub unsafe fn from_raw(raw: MlirIntegerSet) -> Self {
+        Self {
+            raw,
+            _context: Default::default(),
+        }
+    }
+
+    /// Converts an integer set into a raw object.
+    pub const fn to_raw(self) -> MlirIntegerSet {
+        self.raw
+    }
+
+    unsafe fn constraint_unchecked(&self, index: usize) -> (AffineExpr<'c>, bool) {
+        (
+            AffineExpr::from_raw(mlirIntegerSetGetConstraint(self.raw, index as isize)),
+            mlirIntegerSetIsConstraintEq(self.raw, index as isize),
+        )
+    }
+}
+
+impl PartialEq for IntegerSet<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { mlirIntegerSetEqual(self.raw, other.raw) }
+    }
+}
+
+impl Eq for IntegerSet<'_> {}
+
+impl Display for IntegerSet<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let mut data = (formatter, Ok(()));
+
+        unsafe {
+            mlirIntegerSetPrint(
+                self.raw,
+                Some(print_callback),
+                &mut data as *mut _ as *mut c_void,
+            );
+        }
+
+        data.1
+    }
+}
+
+impl Debug for IntegerSet<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::create_test_context;
+    use pretty_assertions::assert_eq;
+
+    fn create_set(context: &Context) -> IntegerSet<'_> {
+        let dim = AffineExpr::dim(context, 0);
+        let symbol = AffineExpr::symbol(context, 0);
+
+        IntegerSet::new(
+            context,
+            1,
+            1,
+            &[
+                dim + AffineExpr::constant(context, -1),
+                symbol + dim * AffineExpr::constant(context, -1),
+                dim + AffineExpr::constant(context, -4),
+            ],
+            &[false, false, true],
+        )
+    }
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+        let set = create_set(&context);
+
+        assert_eq!(
+            set.to_string(),
+            "(d0)[s0] : (d0 - 1 >= 0, -d0 + s0 >= 0, d0 - 4 == 0)"
+        );
+        assert_eq!(set.dim_count(), 1);
+        assert_eq!(set.symbol_count(), 1);
+        assert_eq!(set.input_count(), 2);
+        assert_eq!(set.constraint_count(), 3);
+        assert_eq!(set.equality_count(), 1);
+        assert_eq!(set.inequality_count(), 2);
+        assert!(!set.is_canonical_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_with_invalid_flags() {
+        let context = create_test_context();
+
+        IntegerSet::new(&context, 1, 0, &[AffineExpr::dim(&context, 0)], &[]);
+    }
+
+    #[test]
+    fn empty() {
+        let context = create_test_context();
+        let set = IntegerSet::empty(&context, 1, 0);
+
+        assert!(set.is_canonical_empty());
+        assert_eq!(set.to_string(), "(d0) : (1 == 0)");
+    }
+
+    #[test]
+    fn constraint() {
+        let context = create_test_context();
+        let set = create_set(&context);
+
+        assert_eq!(
+            set.constraint(2),
+            Ok((
+                AffineExpr::dim(&context, 0) + AffineExpr::constant(&context, -4),
+                true
+            ))
+        );
+        assert_eq!(
+            set.constraint(3),
+            Err(Error::PositionOutOfBounds {
+                name: "integer set constraint",
+                value: set.to_string(),
+                index: 3,
+            })
+        );
+        assert_eq!(
+            set.constraints()
+                .map(|(_, equality)| equality)
+                .collect::<Vec<_>>(),
+            [false, false, true]
+        );
+    }
+
+    #[test]
+    fn replace() {
+        let context = create_test_context();
+        let set = create_set(&context);
+
+        assert_eq!(
+            set.replace(
+                &[AffineExpr::dim(&context, 1)],
+                &[AffineExpr::constant(&context, 8)],
+                2,
+                0
+            )
+            .to_string(),
+            "(d0, d1) : (d1 - 1 >= 0, -d1 + 8 >= 0, d1 - 4 == 0)"
+        );
+    }
+
+    #[test]
+    fn equal() {
+        let context = create_test_context();
+
+        assert_eq!(create_set(&context), create_set(&context));
+        assert_ne!(create_set(&context), IntegerSet::empty(&context, 1, 1));
+    }
+}
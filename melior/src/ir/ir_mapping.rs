@@ -0,0 +1,254 @@
+use super::{
+    operation::OperationLike, BlockLike, BlockRef, OperationRef, RegionLike, Value, ValueLike,
+};
+use mlir_sys::{mlirOperationSetOperand, mlirOperationSetSuccessor, MlirBlock, MlirValue};
+use std::{collections::HashMap, ffi::c_void, marker::PhantomData};
+
+/// A mapping of values and blocks used to clone operations and regions.
+///
+/// Values and blocks not in a mapping are kept as they are on cloning.
+/// Cloning records a mapping from original values and blocks to cloned ones.
+#[derive(Debug, Default)]
+pub struct IrMapping<'c, 'a> {
+    values: HashMap<*const c_void, MlirValue>,
+    blocks: HashMap<*const c_void, MlirBlock>,
+    _reference: PhantomData<Value<'c, 'a>>,
+}
+
+impl<'c, 'a> IrMapping<'c, 'a> {
+    /// Creates an empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a value to another.
+    pub fn map_value(&mut self, from: impl ValueLike<'c>, to: impl ValueLike<'c>) {
+        self.values.insert(from.to_raw().ptr as _, to.to_raw());
+    }
+
+    /// Maps a block to another.
+    pub fn map_block<'b>(&mut self, from: &impl BlockLike<'c, 'b>, to: &impl BlockLike<'c, 'b>) {
+        self.blocks.insert(from.to_raw().ptr as _, to.to_raw());
+    }
+
+    /// Returns a mapped value.
+    pub fn lookup_value(&self, value: impl ValueLike<'c>) -> Option<Value<'c, 'a>> {
+        self.values
+            .get(&(value.to_raw().ptr as _))
+            .map(|&value| unsafe { Value::from_raw(value) })
+    }
+
+    /// Returns a mapped block.
+    pub fn lookup_block<'b>(&self, block: &impl BlockLike<'c, 'b>) -> Option<BlockRef<'c, 'a>> {
+        self.blocks
+            .get(&(block.to_raw().ptr as _))
+            .map(|&block| unsafe { BlockRef::from_raw(block) })
+    }
+
+    /// Returns `true` if a value is mapped.
+    pub fn contains_value(&self, value: impl ValueLike<'c>) -> bool {
+        self.values.contains_key(&(value.to_raw().ptr as _))
+    }
+
+    /// Returns `true` if a block is mapped.
+    pub fn contains_block<'b>(&self, block: &impl BlockLike<'c, 'b>) -> bool {
+        self.blocks.contains_key(&(block.to_raw().ptr as _))
+    }
+
+    /// Removes all mapped values and blocks.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.blocks.clear();
+    }
+
+    /// Maps results, blocks, and block arguments of an operation to the ones
+    /// of its clone recursively.
+    pub(crate) fn map_operation(&mut self, from: OperationRef<'c, '_>, to: OperationRef<'c, '_>) {
+        for (from, to) in from.results().zip(to.results()) {
+            self.map_value(from, to);
+        }
+
+        for (from, to) in from.regions().zip(to.regions()) {
+            let mut blocks = (from.first_block(), to.first_block());
+
+            while let (Some(from), Some(to)) = blocks {
+                self.map_block(&from, &to);
+
+                for index in 0..from.argument_count() {
+                    self.map_value(
+                        from.argument(index).expect("valid argument index"),
+                        to.argument(index).expect("valid argument index"),
+                    );
+                }
+
+                let mut operations = (from.first_operation(), to.first_operation());
+
+                while let (Some(from), Some(to)) = operations {
+                    self.map_operation(from, to);
+                    operations = (from.next_in_block(), to.next_in_block());
+                }
+
+                blocks = (from.next_in_region(), to.next_in_region());
+            }
+        }
+    }
+
+    /// Replaces operands and successors of an operation and its nested
+    /// operations with mapped ones.
+    pub(crate) fn remap(&self, operation: OperationRef<'c, '_>) {
+        for (index, operand) in operation.operands().enumerate() {
+            if let Some(&value) = self.values.get(&(operand.to_raw().ptr as _)) {
+                unsafe { mlirOperationSetOperand(operation.to_raw(), index as isize, value) }
+            }
+        }
+
+        for (index, successor) in operation.successors().enumerate() {
+            if let Some(&block) = self.blocks.get(&(successor.to_raw().ptr as _)) {
+                unsafe { mlirOperationSetSuccessor(operation.to_raw(), index as isize, block) }
+            }
+        }
+
+        for region in operation.regions() {
+            let mut block = region.first_block();
+
+            while let Some(current) = block {
+                let mut nested = current.first_operation();
+
+                while let Some(operation) = nested {
+                    self.remap(operation);
+                    nested = operation.next_in_block();
+                }
+
+                block = current.next_in_region();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{operation::OperationBuilder, Block, Location, Region, Type},
+        test::create_test_context,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn map_value() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+        let other_argument = block.argument(1).unwrap();
+
+        let mut mapping = IrMapping::new();
+
+        assert!(!mapping.contains_value(argument));
+
+        mapping.map_value(argument, other_argument);
+
+        assert!(mapping.contains_value(argument));
+        assert_eq!(mapping.lookup_value(argument), Some(other_argument.into()));
+        assert_eq!(mapping.lookup_value(other_argument), None);
+
+        mapping.clear();
+
+        assert!(!mapping.contains_value(argument));
+    }
+
+    #[test]
+    fn map_block() {
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        let mut mapping = IrMapping::new();
+        mapping.map_block(&block, &other_block);
+
+        assert!(mapping.contains_block(&block));
+        assert_eq!(mapping.lookup_block(&block), Some(other_block));
+        assert_eq!(mapping.lookup_block(&other_block), None);
+    }
+
+    #[test]
+    fn clone_with_mapping() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+        let other_argument = block.argument(1).unwrap();
+
+        let operation = block.append_operation(
+            OperationBuilder::new("foo", location)
+                .add_operands(&[argument.into()])
+                .add_results(&[r#type])
+                .build()
+                .unwrap(),
+        );
+
+        let mut mapping = IrMapping::new();
+        mapping.map_value(argument, other_argument);
+
+        let cloned = block.append_operation(operation.clone_with_mapping(&mut mapping));
+
+        assert_eq!(operation.operand(0), Ok(argument.into()));
+        assert_eq!(cloned.operand(0), Ok(other_argument.into()));
+        assert_eq!(
+            mapping.lookup_value(operation.result(0).unwrap()),
+            Some(cloned.result(0).unwrap().into())
+        );
+    }
+
+    #[test]
+    fn clone_with_mapping_nested() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+        let other_argument = block.argument(1).unwrap();
+
+        let operation = block.append_operation(
+            OperationBuilder::new("foo", location)
+                .add_regions([{
+                    let block = Block::new(&[(r#type, location)]);
+                    block.append_operation(
+                        OperationBuilder::new("bar", location)
+                            .add_operands(&[argument.into(), block.argument(0).unwrap().into()])
+                            .build()
+                            .unwrap(),
+                    );
+
+                    let region = Region::new();
+                    region.append_block(block);
+                    region
+                }])
+                .build()
+                .unwrap(),
+        );
+
+        let mut mapping = IrMapping::new();
+        mapping.map_value(argument, other_argument);
+
+        let cloned = block.append_operation(operation.clone_with_mapping(&mut mapping));
+        let cloned_block = cloned.region(0).unwrap().first_block().unwrap();
+        let nested = cloned_block.first_operation().unwrap();
+
+        assert_eq!(nested.operand(0), Ok(other_argument.into()));
+        assert_eq!(
+            nested.operand(1),
+            Ok(cloned_block.argument(0).unwrap().into())
+        );
+        assert_eq!(
+            mapping.lookup_block(&operation.region(0).unwrap().first_block().unwrap()),
+            Some(cloned_block)
+        );
+    }
+}
@@ -1,13 +1,27 @@
-use super::{operation::OperationRefMut, BlockRef, Location, Operation, OperationRef};
+mod llvm_module;
+
+use self::llvm_module::LlvmModule;
+use super::{
+    operation::OperationRefMut, BlockRef, BytecodeWriterConfig, Location, Operation, OperationRef,
+};
 use crate::{
     context::{Context, ContextRef},
+    logical_result::LogicalResult,
     string_ref::StringRef,
+    Error,
 };
 use mlir_sys::{
     mlirModuleCreateEmpty, mlirModuleCreateParse, mlirModuleDestroy, mlirModuleFromOperation,
-    mlirModuleGetBody, mlirModuleGetContext, mlirModuleGetOperation, MlirModule,
+    mlirModuleGetBody, mlirModuleGetContext, mlirModuleGetOperation,
+    mlirOperationWriteBytecodeWithConfig, MlirModule, MlirStringRef,
+};
+use std::{
+    ffi::{c_void, CString},
+    io::{self, Write},
+    marker::PhantomData,
+    mem::forget,
+    slice,
 };
-use std::{ffi::CString, marker::PhantomData};
 
 /// A module.
 #[derive(Debug)]
@@ -32,6 +46,75 @@ impl<'c> Module<'c> {
         unsafe { Self::from_option_raw(mlirModuleCreateParse(context.to_raw(), source.to_raw())) }
     }
 
+    /// Parses a module in bytecode.
+    pub fn parse_bytecode(context: &Context, bytecode: &[u8]) -> Option<Self> {
+        unsafe {
+            Self::from_option_raw(mlirModuleCreateParse(
+                context.to_raw(),
+                MlirStringRef {
+                    data: bytecode.as_ptr() as *const _,
+                    length: bytecode.len(),
+                },
+            ))
+        }
+    }
+
+    /// Writes a module in bytecode.
+    pub fn write_bytecode<W: Write>(
+        &self,
+        writer: &mut W,
+        config: &BytecodeWriterConfig,
+    ) -> Result<(), Error> {
+        unsafe extern "C" fn callback<W: Write>(string: MlirStringRef, data: *mut c_void) {
+            let (writer, result) = &mut *(data as *mut (&mut W, io::Result<()>));
+
+            if result.is_err() {
+                return;
+            }
+
+            *result = writer.write_all(slice::from_raw_parts(
+                string.data as *const u8,
+                string.length,
+            ));
+        }
+
+        let config = config.to_raw();
+        let mut data: (_, io::Result<()>) = (writer, Ok(()));
+
+        let result = LogicalResult::from_raw(unsafe {
+            mlirOperationWriteBytecodeWithConfig(
+                self.as_operation().to_raw(),
+                config.to_raw(),
+                Some(callback::<W>),
+                &mut data as *mut _ as *mut c_void,
+            )
+        });
+
+        data.1?;
+
+        if result.is_success() {
+            Ok(())
+        } else {
+            Err(Error::WriteBytecode)
+        }
+    }
+
+    /// Translates a module in the `llvm` dialect into LLVM IR.
+    ///
+    /// Translations of dialects other than `llvm` need to be registered in a
+    /// context by `register_all_llvm_translations`.
+    pub fn translate_to_llvm_ir(&self) -> Result<String, Error> {
+        LlvmModule::translate(self.as_operation())?.to_ir()
+    }
+
+    /// Translates a module in the `llvm` dialect into LLVM bitcode and writes
+    /// it.
+    pub fn write_llvm_bitcode(&self, writer: &mut impl Write) -> Result<(), Error> {
+        writer.write_all(&LlvmModule::translate(self.as_operation())?.to_bitcode())?;
+
+        Ok(())
+    }
+
     /// Converts a module into an operation.
     pub fn as_operation(&self) -> OperationRef<'c, '_> {
         unsafe { OperationRef::from_raw(mlirModuleGetOperation(self.raw)) }
@@ -86,6 +169,15 @@ impl<'c> Module<'c> {
     pub const fn to_raw(&self) -> MlirModule {
         self.raw
     }
+
+    /// Converts a module into an owned raw object.
+    pub const fn into_raw(self) -> MlirModule {
+        let module = self.raw;
+
+        forget(self);
+
+        module
+    }
 }
 
 impl Drop for Module<'_> {
@@ -105,6 +197,8 @@ mod tests {
         },
         test::create_test_context,
     };
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn new() {
@@ -169,4 +263,115 @@ mod tests {
 
         assert!(module.as_operation().verify());
     }
+
+    #[test]
+    fn write_bytecode() {
+        let context = create_test_context();
+        let module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32) -> i32 {
+                    return %arg0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut bytecode = vec![];
+
+        module
+            .write_bytecode(&mut bytecode, &BytecodeWriterConfig::default())
+            .unwrap();
+
+        assert!(bytecode.starts_with(b"ML\xefR"));
+        assert_eq!(
+            Module::parse_bytecode(&context, &bytecode)
+                .unwrap()
+                .as_operation()
+                .to_string(),
+            module.as_operation().to_string()
+        );
+    }
+
+    #[test]
+    fn write_bytecode_with_unsupported_version() {
+        let context = create_test_context();
+        let module = Module::new(Location::unknown(&context));
+
+        assert_eq!(
+            module.write_bytecode(
+                &mut vec![],
+                &BytecodeWriterConfig {
+                    desired_version: Some(i64::MAX),
+                },
+            ),
+            Err(Error::WriteBytecode)
+        );
+    }
+
+    #[test]
+    fn translate_to_llvm_ir() {
+        let context = create_test_context();
+        let module = Module::parse(
+            &context,
+            indoc!(
+                "
+                llvm.func @foo(%arg0 : i64) -> i64 {
+                    llvm.return %arg0 : i64
+                }
+                "
+            ),
+        )
+        .unwrap();
+
+        let ir = module.translate_to_llvm_ir().unwrap();
+
+        assert!(ir.contains("define i64 @foo(i64 %0) {"));
+        assert!(ir.contains("ret i64 %0"));
+    }
+
+    #[test]
+    fn translate_to_llvm_ir_fail() {
+        let context = create_test_context();
+        let module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo() {
+                    return
+                }
+                "
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(module.translate_to_llvm_ir(), Err(Error::TranslateToLlvmIr));
+    }
+
+    #[test]
+    fn write_llvm_bitcode() {
+        let context = create_test_context();
+        let module = Module::parse(
+            &context,
+            indoc!(
+                "
+                llvm.func @foo() {
+                    llvm.return
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut bitcode = vec![];
+
+        module.write_llvm_bitcode(&mut bitcode).unwrap();
+
+        assert!(bitcode.starts_with(b"BC\xc0\xde"));
+    }
+
+    #[test]
+    fn parse_bytecode_none() {
+        assert!(Module::parse_bytecode(&Context::new(), b"ML\xefR").is_none());
+    }
 }
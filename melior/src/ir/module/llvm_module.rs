@@ -0,0 +1,68 @@
+use crate::{ir::OperationRef, Error};
+use mlir_sys::{
+    mlirTranslateModuleToLLVMIR, LLVMContextCreate, LLVMContextDispose, LLVMContextRef,
+    LLVMDisposeMemoryBuffer, LLVMDisposeMessage, LLVMDisposeModule, LLVMGetBufferSize,
+    LLVMGetBufferStart, LLVMModuleRef, LLVMPrintModuleToString, LLVMWriteBitcodeToMemoryBuffer,
+};
+use std::{ffi::CStr, slice};
+
+/// An LLVM module translated from a module in the `llvm` dialect.
+///
+/// It owns its own LLVM context.
+pub(super) struct LlvmModule {
+    context: LLVMContextRef,
+    module: LLVMModuleRef,
+}
+
+impl LlvmModule {
+    /// Translates a module operation into an LLVM module.
+    pub fn translate(operation: OperationRef) -> Result<Self, Error> {
+        let context = unsafe { LLVMContextCreate() };
+        let module = unsafe { mlirTranslateModuleToLLVMIR(operation.to_raw(), context) };
+
+        if module.is_null() {
+            unsafe { LLVMContextDispose(context) };
+
+            Err(Error::TranslateToLlvmIr)
+        } else {
+            Ok(Self { context, module })
+        }
+    }
+
+    /// Prints a module as LLVM IR.
+    pub fn to_ir(&self) -> Result<String, Error> {
+        unsafe {
+            let raw = LLVMPrintModuleToString(self.module);
+            let string = CStr::from_ptr(raw).to_str().map(String::from);
+
+            LLVMDisposeMessage(raw);
+
+            Ok(string?)
+        }
+    }
+
+    /// Writes a module as LLVM bitcode.
+    pub fn to_bitcode(&self) -> Vec<u8> {
+        unsafe {
+            let buffer = LLVMWriteBitcodeToMemoryBuffer(self.module);
+            let bitcode = slice::from_raw_parts(
+                LLVMGetBufferStart(buffer) as *const u8,
+                LLVMGetBufferSize(buffer),
+            )
+            .to_vec();
+
+            LLVMDisposeMemoryBuffer(buffer);
+
+            bitcode
+        }
+    }
+}
+
+impl Drop for LlvmModule {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeModule(self.module);
+            LLVMContextDispose(self.context);
+        }
+    }
+}
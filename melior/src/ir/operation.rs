@@ -7,7 +7,7 @@ mod result;
 
 pub use self::{
     builder::OperationBuilder,
-    operation_like::{OperationLike, OperationMutLike, WalkOrder, WalkResult},
+    operation_like::{NamedOperation, OperationLike, OperationMutLike, WalkOrder, WalkResult},
     printing_flags::OperationPrintingFlags,
     result::OperationResult,
 };
@@ -21,7 +21,8 @@ use core::{
     mem::{forget, transmute},
 };
 use mlir_sys::{
-    mlirOperationClone, mlirOperationDestroy, mlirOperationEqual, mlirOperationPrint, MlirOperation,
+    mlirOperationClone, mlirOperationDestroy, mlirOperationEqual, mlirOperationMoveAfter,
+    mlirOperationMoveBefore, mlirOperationPrint, MlirOperation,
 };
 use std::{
     ffi::c_void,
@@ -224,7 +225,31 @@ pub struct OperationRefMut<'c, 'a> {
     _reference: PhantomData<&'a Operation<'c>>,
 }
 
-impl OperationRefMut<'_, '_> {
+impl<'c> OperationRefMut<'c, '_> {
+    /// Moves an operation before another.
+    ///
+    /// Both operations must be in blocks.
+    pub fn move_before(&mut self, other: OperationRef<'c, '_>) {
+        unsafe { mlirOperationMoveBefore(self.raw, other.to_raw()) }
+    }
+
+    /// Moves an operation after another.
+    ///
+    /// Both operations must be in blocks.
+    pub fn move_after(&mut self, other: OperationRef<'c, '_>) {
+        unsafe { mlirOperationMoveAfter(self.raw, other.to_raw()) }
+    }
+
+    /// Erases an operation from its parent block and destroys it.
+    ///
+    /// # Safety
+    ///
+    /// No reference to the operation or its results can be used after this
+    /// call, and its results must have no uses.
+    pub unsafe fn erase(self) {
+        mlirOperationDestroy(self.raw)
+    }
+
     /// Converts an operation reference into a raw object.
     pub const fn to_raw(self) -> MlirOperation {
         self.raw
@@ -570,6 +595,173 @@ mod tests {
         );
     }
 
+    #[test]
+    fn move_before_and_after() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let r#return = block.append_operation(
+            OperationBuilder::new("func.return", location)
+                .build()
+                .unwrap(),
+        );
+
+        assert_eq!(foo.is_before_in_block(r#return), Some(true));
+
+        block.terminator_mut().unwrap().move_before(foo);
+
+        assert_eq!(r#return.is_before_in_block(foo), Some(true));
+        assert_eq!(block.first_operation(), Some(r#return));
+
+        block.first_operation_mut().unwrap().move_after(foo);
+
+        assert_eq!(foo.is_before_in_block(r#return), Some(true));
+        assert_eq!(block.first_operation(), Some(foo));
+    }
+
+    #[test]
+    fn is_before_in_different_blocks() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+        let other_block = Block::new(&[]);
+
+        let foo = block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let bar =
+            other_block.append_operation(OperationBuilder::new("bar", location).build().unwrap());
+
+        assert_eq!(foo.is_before_in_block(bar), None);
+    }
+
+    #[test]
+    fn set_operand() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument: Value = block.argument(0).unwrap().into();
+        let other_argument: Value = block.argument(1).unwrap().into();
+
+        let mut operation = OperationBuilder::new("foo", location)
+            .add_operands(&[argument, argument])
+            .build()
+            .unwrap();
+
+        assert_eq!(operation.set_operand(1, other_argument), Ok(()));
+        assert_eq!(
+            operation.operands().collect::<Vec<_>>(),
+            [argument, other_argument]
+        );
+        assert_eq!(
+            operation.set_operand(2, other_argument),
+            Err(Error::PositionOutOfBounds {
+                name: "operand",
+                value: operation.to_string(),
+                index: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn set_operands() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument: Value = block.argument(0).unwrap().into();
+        let other_argument: Value = block.argument(1).unwrap().into();
+
+        let mut operation = OperationBuilder::new("foo", location)
+            .add_operands(&[argument])
+            .build()
+            .unwrap();
+
+        operation.set_operands(&[other_argument, argument, other_argument]);
+
+        assert_eq!(
+            operation.operands().collect::<Vec<_>>(),
+            [other_argument, argument, other_argument]
+        );
+    }
+
+    #[test]
+    fn set_successor() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        let mut operation = OperationBuilder::new("foo", location)
+            .add_successors(&[&block])
+            .build()
+            .unwrap();
+
+        assert_eq!(operation.set_successor(0, &other_block), Ok(()));
+        assert_eq!(operation.successor(0), Ok(other_block));
+        assert_eq!(
+            operation.set_successor(1, &block),
+            Err(Error::PositionOutOfBounds {
+                name: "successor",
+                value: operation.to_string(),
+                index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn replace_uses_of_with() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument: Value = block.argument(0).unwrap().into();
+        let other_argument: Value = block.argument(1).unwrap().into();
+
+        let mut operation = OperationBuilder::new("foo", location)
+            .add_operands(&[argument, other_argument, argument])
+            .build()
+            .unwrap();
+
+        operation.replace_uses_of_with(argument, other_argument);
+
+        assert_eq!(
+            operation.operands().collect::<Vec<_>>(),
+            [other_argument, other_argument, other_argument]
+        );
+    }
+
+    #[test]
+    fn erase() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        block.append_operation(OperationBuilder::new("foo", location).build().unwrap());
+        let bar = block.append_operation(OperationBuilder::new("bar", location).build().unwrap());
+
+        unsafe { block.first_operation_mut().unwrap().erase() };
+
+        assert_eq!(block.first_operation(), Some(bar));
+        assert_eq!(bar.next_in_block(), None);
+    }
+
     #[test]
     fn parent_operation() {
         let context = create_test_context();
@@ -637,6 +829,158 @@ mod tests {
         append(&context, &block, location);
     }
 
+    #[test]
+    fn walk_ops() {
+        struct Child<'c> {
+            operation: Operation<'c>,
+        }
+
+        impl<'c> NamedOperation<'c> for Child<'c> {
+            fn name() -> &'static str {
+                "child"
+            }
+
+            unsafe fn from_ref(operation: OperationRef<'c, '_>) -> Self {
+                Self {
+                    operation: Operation::from_raw(operation.to_raw()),
+                }
+            }
+        }
+
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        let operation = block.append_operation(
+            OperationBuilder::new("child", location)
+                .add_regions([{
+                    let region = Region::new();
+
+                    let block = Block::new(&[]);
+                    block.append_operation(
+                        OperationBuilder::new("other", location).build().unwrap(),
+                    );
+                    block.append_operation(
+                        OperationBuilder::new("parent", location)
+                            .add_regions([{
+                                let region = Region::new();
+
+                                let block = Block::new(&[]);
+                                block.append_operation(
+                                    OperationBuilder::new("child", location).build().unwrap(),
+                                );
+
+                                region.append_block(block);
+                                region
+                            }])
+                            .build()
+                            .unwrap(),
+                    );
+
+                    region.append_block(block);
+                    region
+                }])
+                .build()
+                .unwrap(),
+        );
+
+        let mut count = 0;
+
+        operation.walk_ops::<Child, _>(WalkOrder::PreOrder, |child| {
+            assert_eq!(child.operation.name().as_string_ref().as_str(), Ok("child"));
+            count += 1;
+            WalkResult::Advance
+        });
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn walk_mut_erase() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+
+        for order in [WalkOrder::PreOrder, WalkOrder::PostOrder] {
+            let mut operation = OperationBuilder::new("parent", location)
+                .add_regions([{
+                    let region = Region::new();
+
+                    let block = Block::new(&[]);
+                    block
+                        .append_operation(OperationBuilder::new("dead", location).build().unwrap());
+                    block.append_operation(
+                        OperationBuilder::new("child", location)
+                            .add_regions([{
+                                let region = Region::new();
+
+                                let block = Block::new(&[]);
+                                block.append_operation(
+                                    OperationBuilder::new("dead", location).build().unwrap(),
+                                );
+
+                                region.append_block(block);
+                                region
+                            }])
+                            .build()
+                            .unwrap(),
+                    );
+                    block
+                        .append_operation(OperationBuilder::new("dead", location).build().unwrap());
+
+                    region.append_block(block);
+                    region
+                }])
+                .build()
+                .unwrap();
+
+            let mut names = vec![];
+
+            operation.walk_mut(order, |operation| {
+                let name = operation
+                    .name()
+                    .as_string_ref()
+                    .as_str()
+                    .unwrap()
+                    .to_owned();
+
+                if name == "dead" {
+                    unsafe { operation.erase() };
+
+                    return WalkResult::Skip;
+                }
+
+                names.push(name);
+                WalkResult::Advance
+            });
+
+            assert_eq!(
+                names,
+                match order {
+                    WalkOrder::PreOrder => ["parent", "child"],
+                    WalkOrder::PostOrder => ["child", "parent"],
+                }
+            );
+
+            let block = operation.region(0).unwrap().first_block().unwrap();
+            let child = block.first_operation().unwrap();
+
+            assert_eq!(child.next_in_block(), None);
+            assert_eq!(
+                child
+                    .region(0)
+                    .unwrap()
+                    .first_block()
+                    .unwrap()
+                    .first_operation(),
+                None
+            );
+        }
+    }
+
     #[test]
     fn walk_pre() {
         let pre = operation_like::WalkOrder::PreOrder;
@@ -1,21 +1,26 @@
-use std::{ffi::c_void, fmt::Display};
+use std::{ffi::c_void, fmt::Display, mem::ManuallyDrop};
 
 use mlir_sys::{
-    mlirOperationDump, mlirOperationGetAttribute, mlirOperationGetAttributeByName,
-    mlirOperationGetBlock, mlirOperationGetContext, mlirOperationGetLocation, mlirOperationGetName,
-    mlirOperationGetNextInBlock, mlirOperationGetNumAttributes, mlirOperationGetNumOperands,
-    mlirOperationGetNumRegions, mlirOperationGetNumResults, mlirOperationGetNumSuccessors,
-    mlirOperationGetOperand, mlirOperationGetParentOperation, mlirOperationGetRegion,
-    mlirOperationGetResult, mlirOperationGetSuccessor, mlirOperationPrintWithFlags,
+    mlirOperationClone, mlirOperationDump, mlirOperationGetAttribute,
+    mlirOperationGetAttributeByName, mlirOperationGetBlock, mlirOperationGetContext,
+    mlirOperationGetLocation, mlirOperationGetName, mlirOperationGetNextInBlock,
+    mlirOperationGetNumAttributes, mlirOperationGetNumOperands, mlirOperationGetNumRegions,
+    mlirOperationGetNumResults, mlirOperationGetNumSuccessors, mlirOperationGetOperand,
+    mlirOperationGetParentOperation, mlirOperationGetRegion, mlirOperationGetResult,
+    mlirOperationGetSuccessor, mlirOperationIsBeforeInBlock, mlirOperationPrintWithFlags,
     mlirOperationRemoveAttributeByName, mlirOperationRemoveFromParent,
-    mlirOperationSetAttributeByName, mlirOperationVerify, mlirOperationWalk, MlirOperation,
-    MlirWalkOrder_MlirWalkPostOrder, MlirWalkOrder_MlirWalkPreOrder, MlirWalkResult,
+    mlirOperationReplaceUsesOfWith, mlirOperationSetAttributeByName, mlirOperationSetOperand,
+    mlirOperationSetOperands, mlirOperationSetSuccessor, mlirOperationVerify, mlirOperationWalk,
+    MlirOperation, MlirWalkOrder_MlirWalkPostOrder, MlirWalkOrder_MlirWalkPreOrder, MlirWalkResult,
     MlirWalkResult_MlirWalkResultAdvance, MlirWalkResult_MlirWalkResultInterrupt,
     MlirWalkResult_MlirWalkResultSkip,
 };
 
 use crate::{
-    ir::{Attribute, AttributeLike, BlockRef, Identifier, Location, RegionRef, Value},
+    ir::{
+        Attribute, AttributeLike, BlockLike, BlockRef, Identifier, IrMapping, Location, Operation,
+        RegionRef, Value, ValueLike,
+    },
     ContextRef, Error, StringRef,
 };
 
@@ -45,6 +50,22 @@ pub enum WalkResult {
     Skip = MlirWalkResult_MlirWalkResultSkip,
 }
 
+/// A trait for typed operations with fixed operation names.
+///
+/// It is implemented by operation types generated from ODS.
+pub trait NamedOperation<'c>: Sized {
+    /// Returns an operation name.
+    fn name() -> &'static str;
+
+    /// Creates a typed operation from a reference to an operation.
+    ///
+    /// # Safety
+    ///
+    /// An operation must have the operation name. The typed operation must not
+    /// be dropped unless it owns the operation.
+    unsafe fn from_ref(operation: OperationRef<'c, '_>) -> Self;
+}
+
 pub trait OperationLike<'c: 'a, 'a>: Display + 'a {
     /// Converts a value into a raw value.
     fn to_raw(&self) -> MlirOperation;
@@ -231,11 +252,41 @@ pub trait OperationLike<'c: 'a, 'a>: Display + 'a {
         todo!("mlirOperationGetPrevInBlock is not exposed in the C API")
     }
 
+    /// Returns `true` if an operation is before another in the same block.
+    ///
+    /// It returns `None` if the operations are not in the same block.
+    fn is_before_in_block(&self, other: OperationRef<'c, '_>) -> Option<bool> {
+        let block = self.block()?;
+
+        (other.block()? == block)
+            .then(|| unsafe { mlirOperationIsBeforeInBlock(self.to_raw(), other.to_raw()) })
+    }
+
     /// Returns a reference to a parent operation.
     fn parent_operation(&self) -> Option<OperationRef<'c, '_>> {
         unsafe { OperationRef::from_option_raw(mlirOperationGetParentOperation(self.to_raw())) }
     }
 
+    /// Clones an operation with a mapping.
+    ///
+    /// Operands and successors of the operation and its nested operations
+    /// are replaced with mapped ones. Then, results, blocks, and block
+    /// arguments of the operation are mapped to the ones of its clone.
+    fn clone_with_mapping(&self, mapping: &mut IrMapping<'c, '_>) -> Operation<'c> {
+        unsafe {
+            let operation = Operation::from_raw(mlirOperationClone(self.to_raw()));
+            let (from, to) = (
+                OperationRef::from_raw(self.to_raw()),
+                OperationRef::from_raw(operation.to_raw()),
+            );
+
+            mapping.remap(to);
+            mapping.map_operation(from, to);
+
+            operation
+        }
+    }
+
     /// Verifies an operation.
     fn verify(&self) -> bool {
         unsafe { mlirOperationVerify(self.to_raw()) }
@@ -293,6 +344,31 @@ pub trait OperationLike<'c: 'a, 'a>: Display + 'a {
             );
         }
     }
+
+    /// Walks operations of a type in this operation and its nested operations
+    /// in either pre- or post-order.
+    ///
+    /// Operations with other names are skipped, but their nested operations
+    /// are still visited.
+    fn walk_ops<T: NamedOperation<'c>, F>(&self, order: WalkOrder, mut callback: F)
+    where
+        F: FnMut(&T) -> WalkResult,
+    {
+        let name = StringRef::new(T::name());
+
+        self.walk(order, |operation: OperationRef| {
+            if operation.name().as_string_ref() == name {
+                // The typed operation does not own the operation.
+                let operation = ManuallyDrop::new(unsafe {
+                    T::from_ref(OperationRef::from_raw(operation.to_raw()))
+                });
+
+                callback(&operation)
+            } else {
+                WalkResult::Advance
+            }
+        });
+    }
 }
 
 pub trait OperationMutLike<'c: 'a, 'a>: OperationLike<'c, 'a> {
@@ -318,4 +394,84 @@ pub trait OperationMutLike<'c: 'a, 'a>: OperationLike<'c, 'a> {
     fn remove_from_parent(&mut self) {
         unsafe { mlirOperationRemoveFromParent(self.to_raw()) }
     }
+
+    /// Sets an operand at a position.
+    fn set_operand(&mut self, index: usize, value: impl ValueLike<'c>) -> Result<(), Error> {
+        if index < self.operand_count() {
+            unsafe { mlirOperationSetOperand(self.to_raw(), index as isize, value.to_raw()) }
+
+            Ok(())
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "operand",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
+    /// Replaces all operands.
+    fn set_operands(&mut self, values: &[Value<'c, '_>]) {
+        unsafe {
+            mlirOperationSetOperands(
+                self.to_raw(),
+                values.len() as isize,
+                values.as_ptr() as *const _,
+            )
+        }
+    }
+
+    /// Sets a successor at a position.
+    fn set_successor<'b>(
+        &mut self,
+        index: usize,
+        block: &impl BlockLike<'c, 'b>,
+    ) -> Result<(), Error> {
+        if index < self.successor_count() {
+            unsafe { mlirOperationSetSuccessor(self.to_raw(), index as isize, block.to_raw()) }
+
+            Ok(())
+        } else {
+            Err(Error::PositionOutOfBounds {
+                name: "successor",
+                value: self.to_string(),
+                index,
+            })
+        }
+    }
+
+    /// Replaces uses of a value with another in operands of an operation.
+    fn replace_uses_of_with(&mut self, of: impl ValueLike<'c>, with: impl ValueLike<'c>) {
+        unsafe { mlirOperationReplaceUsesOfWith(self.to_raw(), of.to_raw(), with.to_raw()) }
+    }
+
+    /// Walks this operation and its nested operations in either pre- or
+    /// post-order with mutable references.
+    ///
+    /// In a post-order walk, the callback can erase a visited operation other
+    /// than this operation with [`OperationRefMut::erase`]. In a pre-order
+    /// walk, it can do so only if it returns `WalkResult::Skip` so that
+    /// nested operations of the erased operation are not visited.
+    fn walk_mut<F>(&mut self, order: WalkOrder, mut callback: F)
+    where
+        F: for<'x, 'y> FnMut(OperationRefMut<'x, 'y>) -> WalkResult,
+    {
+        unsafe extern "C" fn tramp<'c: 'a, 'a, F: FnMut(OperationRefMut<'c, 'a>) -> WalkResult>(
+            operation: MlirOperation,
+            data: *mut c_void,
+        ) -> MlirWalkResult {
+            let callback: &mut F = &mut *(data as *mut F);
+
+            (callback)(OperationRefMut::from_raw(operation)) as _
+        }
+
+        unsafe {
+            mlirOperationWalk(
+                self.to_raw(),
+                Some(tramp::<'c, 'a, F>),
+                &mut callback as *mut _ as *mut _,
+                order as _,
+            );
+        }
+    }
 }
@@ -120,6 +120,14 @@ impl Eq for RegionRef<'_, '_> {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        ir::{
+            operation::{OperationBuilder, OperationLike, WalkOrder, WalkResult},
+            BlockLike, IrMapping, Location, Type,
+        },
+        test::create_test_context,
+    };
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn new() {
@@ -161,6 +169,111 @@ mod tests {
         assert_eq!(region.first_block(), Some(block));
     }
 
+    #[test]
+    fn blocks() {
+        let region = Region::new();
+
+        let block = region.append_block(Block::new(&[]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        assert_eq!(region.blocks().collect::<Vec<_>>(), [block, other_block]);
+        assert_eq!(
+            region.blocks_rev().collect::<Vec<_>>(),
+            [other_block, block]
+        );
+        assert_eq!(Region::new().blocks().count(), 0);
+    }
+
+    #[test]
+    fn walk_mut() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let region = Region::new();
+
+        for name in ["foo", "bar"] {
+            let block = region.append_block(Block::new(&[]));
+            block.append_operation(OperationBuilder::new(name, location).build().unwrap());
+        }
+
+        let mut names = vec![];
+
+        assert_eq!(
+            region.walk_mut(WalkOrder::PreOrder, |operation| {
+                names.push(
+                    operation
+                        .name()
+                        .as_string_ref()
+                        .as_str()
+                        .unwrap()
+                        .to_owned(),
+                );
+                WalkResult::Advance
+            }),
+            WalkResult::Advance
+        );
+        assert_eq!(names, ["foo", "bar"]);
+    }
+
+    #[test]
+    fn take_body() {
+        let region = Region::new();
+        region.append_block(Block::new(&[]));
+
+        let other_region = Region::new();
+        let block = other_region.append_block(Block::new(&[]));
+        other_region.append_block(Block::new(&[]));
+
+        unsafe { region.take_body(&other_region) };
+
+        assert_eq!(region.first_block(), Some(block));
+        assert!(block.next_in_region().is_some());
+        assert_eq!(other_region.first_block(), None);
+    }
+
+    #[test]
+    fn clone_into() {
+        let context = create_test_context();
+        context.set_allow_unregistered_dialects(true);
+
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let region = Region::new();
+        let block = region.append_block(Block::new(&[(r#type, location)]));
+        let other_block = region.append_block(Block::new(&[]));
+
+        block.append_operation(
+            OperationBuilder::new("foo", location)
+                .add_operands(&[block.argument(0).unwrap().into()])
+                .add_successors(&[&other_block])
+                .build()
+                .unwrap(),
+        );
+
+        let other_region = Region::new();
+        let mut mapping = IrMapping::new();
+
+        region.clone_into(&other_region, &mut mapping);
+
+        let cloned_block = other_region.first_block().unwrap();
+        let cloned_other_block = cloned_block.next_in_region().unwrap();
+        let operation = cloned_block.first_operation().unwrap();
+
+        assert_eq!(mapping.lookup_block(&block), Some(cloned_block));
+        assert_eq!(mapping.lookup_block(&other_block), Some(cloned_other_block));
+        assert_eq!(cloned_block.argument_count(), 1);
+        assert_eq!(
+            operation.operand(0),
+            Ok(cloned_block.argument(0).unwrap().into())
+        );
+        assert_eq!(operation.successor(0), Ok(cloned_other_block));
+        assert_eq!(
+            block.first_operation().unwrap().successor(0),
+            Ok(other_block)
+        );
+    }
+
     #[test]
     fn equal() {
         let region = Region::new();
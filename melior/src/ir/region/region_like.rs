@@ -1,8 +1,12 @@
-use crate::ir::{Block, BlockRef};
+use crate::ir::{
+    operation::{OperationLike, OperationRefMut, WalkOrder, WalkResult},
+    Block, BlockLike, BlockRef, IrMapping, Location, ValueLike,
+};
 use mlir_sys::{
     mlirRegionAppendOwnedBlock, mlirRegionGetFirstBlock, mlirRegionInsertOwnedBlockAfter,
-    mlirRegionInsertOwnedBlockBefore, MlirRegion,
+    mlirRegionInsertOwnedBlockBefore, mlirRegionTakeBody, mlirValueGetLocation, MlirRegion,
 };
+use std::iter::successors;
 
 /// A region-like trait.
 pub trait RegionLike<'c, 'a>: 'a {
@@ -22,6 +26,25 @@ pub trait RegionLike<'c, 'a>: 'a {
         }
     }
 
+    /// Returns all blocks.
+    fn blocks(&self) -> impl Iterator<Item = BlockRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        successors(self.first_block(), |block| block.next_in_region())
+    }
+
+    /// Returns all blocks in reverse order.
+    ///
+    /// Blocks are collected eagerly as the C API does not provide backward
+    /// traversal.
+    fn blocks_rev(&self) -> impl Iterator<Item = BlockRef<'c, 'a>>
+    where
+        'c: 'a,
+    {
+        self.blocks().collect::<Vec<_>>().into_iter().rev()
+    }
+
     /// Inserts a block after another block.
     fn insert_block_after(&self, one: BlockRef<'c, 'a>, other: Block<'c>) -> BlockRef<'c, 'a> {
         unsafe {
@@ -54,4 +77,91 @@ pub trait RegionLike<'c, 'a>: 'a {
             r#ref
         }
     }
+
+    /// Takes blocks of another region and replaces the ones of a region with
+    /// them.
+    ///
+    /// The other region becomes empty.
+    ///
+    /// # Safety
+    ///
+    /// No reference to the original blocks of the region can be used after
+    /// this call.
+    unsafe fn take_body<'b>(&self, region: &impl RegionLike<'c, 'b>) {
+        mlirRegionTakeBody(self.to_raw(), region.to_raw())
+    }
+
+    /// Clones blocks of a region into the end of another region with a
+    /// mapping.
+    ///
+    /// Blocks, block arguments, and results of operations in the region are
+    /// mapped to the cloned ones.
+    fn clone_into<'b>(&self, region: &impl RegionLike<'c, 'b>, mapping: &mut IrMapping<'c, '_>)
+    where
+        'c: 'a + 'b,
+    {
+        // Collect blocks first as a region can be cloned into itself.
+        let blocks = self
+            .blocks()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|block| {
+                let cloned = region.append_block(Block::new(
+                    &(0..block.argument_count())
+                        .map(|index| {
+                            let argument = block.argument(index).expect("valid argument index");
+
+                            (argument.r#type(), unsafe {
+                                Location::from_raw(mlirValueGetLocation(argument.to_raw()))
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                ));
+
+                mapping.map_block(&block, &cloned);
+
+                for index in 0..block.argument_count() {
+                    mapping.map_value(
+                        block.argument(index).expect("valid argument index"),
+                        cloned.argument(index).expect("valid argument index"),
+                    );
+                }
+
+                (block, cloned)
+            })
+            .collect::<Vec<_>>();
+
+        for (block, cloned) in &blocks {
+            for operation in block.operations() {
+                cloned.append_operation(operation.clone_with_mapping(mapping));
+            }
+        }
+
+        // Remap operands again as values can be used before their definitions
+        // across blocks.
+        for operation in blocks.iter().flat_map(|(_, cloned)| cloned.operations()) {
+            mapping.remap(operation);
+        }
+    }
+
+    /// Walks operations in a region and their nested operations in either
+    /// pre- or post-order with mutable references.
+    ///
+    /// The same rules as
+    /// [`OperationMutLike::walk_mut`](crate::ir::operation::OperationMutLike::walk_mut)
+    /// apply to erasure of visited operations. It returns
+    /// `WalkResult::Interrupt` if the walk is interrupted.
+    fn walk_mut<F>(&self, order: WalkOrder, mut callback: F) -> WalkResult
+    where
+        'c: 'a,
+        F: for<'x, 'y> FnMut(OperationRefMut<'x, 'y>) -> WalkResult,
+    {
+        for block in self.blocks() {
+            if block.walk_mut(order, &mut callback) == WalkResult::Interrupt {
+                return WalkResult::Interrupt;
+            }
+        }
+
+        WalkResult::Advance
+    }
 }
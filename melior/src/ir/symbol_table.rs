@@ -0,0 +1,272 @@
+use super::{attribute::StringAttribute, Attribute, Operation, OperationRef};
+use crate::{logical_result::LogicalResult, string_ref::StringRef, Error};
+use mlir_sys::{
+    mlirSymbolTableCreate, mlirSymbolTableDestroy, mlirSymbolTableErase,
+    mlirSymbolTableGetSymbolAttributeName, mlirSymbolTableGetVisibilityAttributeName,
+    mlirSymbolTableInsert, mlirSymbolTableLookup, mlirSymbolTableReplaceAllSymbolUses,
+    mlirSymbolTableWalkSymbolTables, MlirOperation, MlirSymbolTable,
+};
+use std::{ffi::c_void, marker::PhantomData};
+
+/// A symbol table.
+///
+/// A symbol table caches symbols defined in a body of an operation with the
+/// `SymbolTable` trait, such as `builtin.module`.
+pub struct SymbolTable<'c, 'a> {
+    raw: MlirSymbolTable,
+    _operation: PhantomData<&'a Operation<'c>>,
+}
+
+impl<'c, 'a> SymbolTable<'c, 'a> {
+    /// Creates a symbol table of an operation.
+    ///
+    /// It returns `None` if the operation is not a symbol table.
+    pub fn new(operation: OperationRef<'c, 'a>) -> Option<Self> {
+        let raw = unsafe { mlirSymbolTableCreate(operation.to_raw()) };
+
+        if raw.ptr.is_null() {
+            None
+        } else {
+            Some(Self {
+                raw,
+                _operation: Default::default(),
+            })
+        }
+    }
+
+    /// Returns a name of symbol attributes.
+    pub fn symbol_attribute_name() -> &'static str {
+        unsafe { StringRef::from_raw(mlirSymbolTableGetSymbolAttributeName()) }
+            .as_str()
+            .expect("valid symbol attribute name")
+    }
+
+    /// Returns a name of symbol visibility attributes.
+    pub fn visibility_attribute_name() -> &'static str {
+        unsafe { StringRef::from_raw(mlirSymbolTableGetVisibilityAttributeName()) }
+            .as_str()
+            .expect("valid visibility attribute name")
+    }
+
+    /// Looks up a symbol with a name.
+    pub fn lookup(&self, name: &str) -> Option<OperationRef<'c, 'a>> {
+        unsafe {
+            OperationRef::from_option_raw(mlirSymbolTableLookup(
+                self.raw,
+                StringRef::new(name).to_raw(),
+            ))
+        }
+    }
+
+    /// Inserts a symbol operation into a body of a symbol table operation.
+    ///
+    /// The symbol is renamed if its name conflicts with existing ones. It
+    /// returns the final name of the symbol.
+    pub fn insert(&self, operation: Operation<'c>) -> StringAttribute<'c> {
+        unsafe { Attribute::from_raw(mlirSymbolTableInsert(self.raw, operation.into_raw())) }
+            .try_into()
+            .expect("string attribute")
+    }
+
+    /// Removes a symbol operation from a symbol table and erases it.
+    ///
+    /// # Safety
+    ///
+    /// No reference to the operation or its nested operations can be used
+    /// after this call, and its results must have no uses.
+    pub unsafe fn erase(&self, operation: OperationRef<'c, 'a>) {
+        unsafe { mlirSymbolTableErase(self.raw, operation.to_raw()) }
+    }
+
+    /// Replaces all uses of a symbol with a new one in nested operations of
+    /// an operation.
+    pub fn replace_all_symbol_uses(
+        old: &str,
+        new: &str,
+        operation: OperationRef<'c, '_>,
+    ) -> Result<(), Error> {
+        let result = LogicalResult::from_raw(unsafe {
+            mlirSymbolTableReplaceAllSymbolUses(
+                StringRef::new(old).to_raw(),
+                StringRef::new(new).to_raw(),
+                operation.to_raw(),
+            )
+        });
+
+        if result.is_success() {
+            Ok(())
+        } else {
+            Err(Error::ReplaceSymbolUses(old.into()))
+        }
+    }
+
+    /// Walks all symbol table operations nested in an operation including
+    /// itself.
+    ///
+    /// The callback receives each symbol table operation and `true` if all
+    /// uses of its symbols are visible.
+    pub fn walk_symbol_tables<F>(
+        operation: OperationRef<'c, '_>,
+        all_symbol_uses_visible: bool,
+        mut callback: F,
+    ) where
+        F: for<'x, 'y> FnMut(OperationRef<'x, 'y>, bool),
+    {
+        unsafe extern "C" fn trampoline<F: for<'x, 'y> FnMut(OperationRef<'x, 'y>, bool)>(
+            operation: MlirOperation,
+            all_uses_visible: bool,
+            data: *mut c_void,
+        ) {
+            (*(data as *mut F))(OperationRef::from_raw(operation), all_uses_visible);
+        }
+
+        unsafe {
+            mlirSymbolTableWalkSymbolTables(
+                operation.to_raw(),
+                all_symbol_uses_visible,
+                Some(trampoline::<F>),
+                &mut callback as *mut _ as *mut c_void,
+            )
+        }
+    }
+}
+
+impl Drop for SymbolTable<'_, '_> {
+    fn drop(&mut self) {
+        unsafe { mlirSymbolTableDestroy(self.raw) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::func,
+        ir::{
+            attribute::TypeAttribute, operation::OperationLike, r#type::FunctionType, BlockLike,
+            Identifier, Location, Module, Region,
+        },
+        test::create_test_context,
+        Context,
+    };
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn parse_module(context: &Context) -> Module<'_> {
+        Module::parse(
+            context,
+            indoc!(
+                "
+                func.func @foo() {
+                    return
+                }
+
+                func.func @bar() {
+                    call @foo() : () -> ()
+                    return
+                }
+                "
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+        let module = parse_module(&context);
+
+        assert!(SymbolTable::new(module.as_operation()).is_some());
+        assert!(SymbolTable::new(module.body().first_operation().unwrap()).is_none());
+    }
+
+    #[test]
+    fn attribute_names() {
+        assert_eq!(SymbolTable::symbol_attribute_name(), "sym_name");
+        assert_eq!(SymbolTable::visibility_attribute_name(), "sym_visibility");
+    }
+
+    #[test]
+    fn lookup() {
+        let context = create_test_context();
+        let module = parse_module(&context);
+        let table = SymbolTable::new(module.as_operation()).unwrap();
+
+        assert_eq!(table.lookup("foo"), module.body().first_operation());
+        assert_eq!(table.lookup("baz"), None);
+    }
+
+    #[test]
+    fn insert() {
+        let context = create_test_context();
+        let module = parse_module(&context);
+        let table = SymbolTable::new(module.as_operation()).unwrap();
+
+        let name = table.insert(func::func(
+            &context,
+            StringAttribute::new(&context, "foo"),
+            TypeAttribute::new(FunctionType::new(&context, &[], &[]).into()),
+            Region::new(),
+            &[(
+                Identifier::new(&context, "sym_visibility"),
+                StringAttribute::new(&context, "private").into(),
+            )],
+            Location::unknown(&context),
+        ));
+
+        assert_ne!(name.value(), "foo");
+        assert!(table.lookup(name.value()).is_some());
+        assert!(module.as_operation().verify());
+    }
+
+    #[test]
+    fn erase() {
+        let context = create_test_context();
+        let module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func private @foo()
+                "
+            ),
+        )
+        .unwrap();
+        let table = SymbolTable::new(module.as_operation()).unwrap();
+
+        unsafe { table.erase(table.lookup("foo").unwrap()) };
+
+        assert_eq!(table.lookup("foo"), None);
+        assert_eq!(module.body().first_operation(), None);
+    }
+
+    #[test]
+    fn replace_all_symbol_uses() {
+        let context = create_test_context();
+        let module = parse_module(&context);
+
+        SymbolTable::replace_all_symbol_uses("foo", "baz", module.as_operation()).unwrap();
+
+        assert!(module.as_operation().to_string().contains("call @baz()"));
+    }
+
+    #[test]
+    fn walk_symbol_tables() {
+        let context = create_test_context();
+        let module = parse_module(&context);
+        let mut names = vec![];
+
+        SymbolTable::walk_symbol_tables(module.as_operation(), true, |operation, visible| {
+            assert!(visible);
+            names.push(
+                operation
+                    .name()
+                    .as_string_ref()
+                    .as_str()
+                    .unwrap()
+                    .to_owned(),
+            );
+        });
+
+        assert_eq!(names, ["builtin.module"]);
+    }
+}
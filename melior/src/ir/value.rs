@@ -1,6 +1,7 @@
+mod operand;
 mod value_like;
 
-pub use self::value_like::ValueLike;
+pub use self::{operand::OpOperand, value_like::ValueLike};
 use super::{block::BlockArgument, operation::OperationResult, Type};
 use crate::{utility::print_callback, Context};
 use mlir_sys::{mlirValueEqual, mlirValuePrint, MlirValue};
@@ -79,6 +80,7 @@ from_borrowed_subtypes!(Value, BlockArgument, OperationResult);
 mod tests {
     use super::*;
     use crate::{
+        dialect::arith,
         ir::{
             block::BlockLike,
             operation::{OperationBuilder, OperationLike},
@@ -151,6 +153,92 @@ mod tests {
         value.result(0).unwrap().dump();
     }
 
+    #[test]
+    fn uses() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let block = Block::new(&[(Type::index(&context), location)]);
+        let argument = block.argument(0).unwrap();
+
+        assert!(argument.use_empty());
+        assert!(!argument.has_one_use());
+
+        let operation =
+            block.append_operation(arith::addi(argument.into(), argument.into(), location));
+
+        let mut operand_numbers = argument
+            .uses()
+            .map(|operand| {
+                assert_eq!(operand.owner(), operation);
+                assert_eq!(operand.value(), argument.into());
+
+                operand.operand_number()
+            })
+            .collect::<Vec<_>>();
+        operand_numbers.sort();
+
+        assert_eq!(operand_numbers, [0, 1]);
+        assert!(!argument.use_empty());
+        assert!(!argument.has_one_use());
+    }
+
+    #[test]
+    fn has_one_use() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+
+        block.append_operation(arith::addi(
+            argument.into(),
+            block.argument(1).unwrap().into(),
+            location,
+        ));
+
+        assert!(argument.has_one_use());
+    }
+
+    #[test]
+    fn replace_all_uses_with() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+        let other_argument = block.argument(1).unwrap();
+
+        let operation =
+            block.append_operation(arith::addi(argument.into(), argument.into(), location));
+
+        argument.replace_all_uses_with(other_argument);
+
+        assert!(argument.use_empty());
+        assert_eq!(operation.operand(0).unwrap(), other_argument.into());
+        assert_eq!(operation.operand(1).unwrap(), other_argument.into());
+    }
+
+    #[test]
+    fn replace_all_uses_except() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let r#type = Type::index(&context);
+        let block = Block::new(&[(r#type, location), (r#type, location)]);
+        let argument = block.argument(0).unwrap();
+        let other_argument = block.argument(1).unwrap();
+
+        let operation =
+            block.append_operation(arith::addi(argument.into(), argument.into(), location));
+        let other_operation =
+            block.append_operation(arith::addi(argument.into(), argument.into(), location));
+
+        argument.replace_all_uses_except(other_argument, operation);
+
+        assert_eq!(operation.operand(0).unwrap(), argument.into());
+        assert_eq!(other_operation.operand(0).unwrap(), other_argument.into());
+        assert_eq!(argument.uses().count(), 2);
+    }
+
     #[test]
     fn equal() {
         let context = create_test_context();
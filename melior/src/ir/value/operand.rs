@@ -0,0 +1,70 @@
+use crate::{
+    ir::{OperationRef, Value},
+    Context,
+};
+use mlir_sys::{
+    mlirOpOperandGetNextUse, mlirOpOperandGetOperandNumber, mlirOpOperandGetOwner,
+    mlirOpOperandGetValue, MlirOpOperand,
+};
+use std::marker::PhantomData;
+
+/// An operation operand, which is a use of a value.
+#[derive(Clone, Copy, Debug)]
+pub struct OpOperand<'c, 'a> {
+    raw: MlirOpOperand,
+    _context: PhantomData<&'c Context>,
+    _parent: PhantomData<&'a ()>,
+}
+
+impl<'c, 'a> OpOperand<'c, 'a> {
+    /// Returns an owner operation.
+    pub fn owner(&self) -> OperationRef<'c, 'a> {
+        unsafe { OperationRef::from_raw(mlirOpOperandGetOwner(self.raw)) }
+    }
+
+    /// Returns an operand number in an owner operation.
+    pub fn operand_number(&self) -> usize {
+        unsafe { mlirOpOperandGetOperandNumber(self.raw) as usize }
+    }
+
+    /// Returns a used value.
+    pub fn value(&self) -> Value<'c, 'a> {
+        unsafe { Value::from_raw(mlirOpOperandGetValue(self.raw)) }
+    }
+
+    /// Returns the next use of the same value.
+    pub fn next_use(&self) -> Option<Self> {
+        unsafe { Self::from_option_raw(mlirOpOperandGetNextUse(self.raw)) }
+    }
+
+    /// Creates an operation operand from a raw object.
+    ///
+    /// # Safety
+    ///
+    /// A raw object must be valid.
+    pub unsafe fn from_raw(raw: MlirOpOperand) -> Self {
+        Self {
+            raw,
+            _context: Default::default(),
+            _parent: Default::default(),
+        }
+    }
+
+    /// Creates an optional operation operand from a raw object.
+    ///
+    /// # Safety
+    ///
+    /// A raw object must be valid.
+    pub unsafe fn from_option_raw(raw: MlirOpOperand) -> Option<Self> {
+        if raw.ptr.is_null() {
+            None
+        } else {
+            Some(Self::from_raw(raw))
+        }
+    }
+
+    /// Converts an operation operand into a raw object.
+    pub const fn to_raw(self) -> MlirOpOperand {
+        self.raw
+    }
+}
@@ -1,7 +1,10 @@
-use super::Type;
+use super::{OpOperand, Type};
+use crate::ir::OperationRef;
 use mlir_sys::{
-    mlirValueDump, mlirValueGetType, mlirValueIsABlockArgument, mlirValueIsAOpResult, MlirValue,
+    mlirValueDump, mlirValueGetFirstUse, mlirValueGetType, mlirValueIsABlockArgument,
+    mlirValueIsAOpResult, mlirValueReplaceAllUsesExcept, mlirValueReplaceAllUsesOfWith, MlirValue,
 };
+use std::iter::successors;
 
 /// A trait for value-like types.
 pub trait ValueLike<'c> {
@@ -23,6 +26,39 @@ pub trait ValueLike<'c> {
         unsafe { mlirValueIsAOpResult(self.to_raw()) }
     }
 
+    /// Returns all uses of a value.
+    fn uses(&self) -> impl Iterator<Item = OpOperand<'c, '_>> {
+        successors(
+            unsafe { OpOperand::from_option_raw(mlirValueGetFirstUse(self.to_raw())) },
+            OpOperand::next_use,
+        )
+    }
+
+    /// Returns `true` if a value has exactly one use.
+    fn has_one_use(&self) -> bool {
+        let mut uses = self.uses();
+
+        uses.next().is_some() && uses.next().is_none()
+    }
+
+    /// Returns `true` if a value has no use.
+    fn use_empty(&self) -> bool {
+        self.uses().next().is_none()
+    }
+
+    /// Replaces all uses of a value with another value.
+    fn replace_all_uses_with(&self, value: impl ValueLike<'c>) {
+        unsafe { mlirValueReplaceAllUsesOfWith(self.to_raw(), value.to_raw()) }
+    }
+
+    /// Replaces all uses of a value with another value except ones in an
+    /// operation.
+    fn replace_all_uses_except(&self, value: impl ValueLike<'c>, operation: OperationRef<'c, '_>) {
+        let mut operation = operation.to_raw();
+
+        unsafe { mlirValueReplaceAllUsesExcept(self.to_raw(), value.to_raw(), 1, &mut operation) }
+    }
+
     /// Dumps a value.
     fn dump(&self) {
         unsafe { mlirValueDump(self.to_raw()) }
@@ -14,6 +14,7 @@ pub mod helpers;
 pub mod ir;
 mod logical_result;
 pub mod pass;
+pub mod rewrite;
 mod string_ref;
 
 #[cfg(test)]
@@ -23,11 +24,14 @@ pub mod utility;
 pub use self::{
     context::{Context, ContextRef},
     error::Error,
-    execution_engine::ExecutionEngine,
+    execution_engine::{
+        ExecutionEngine, JitArgument, JitClosure, JitFunction, JitResult, JitReturn, JitType,
+        StridedMemRef, UnrankedMemRef,
+    },
     string_ref::StringRef,
 };
 
-pub use melior_macro::dialect;
+pub use melior_macro::{dialect, PassOptions};
 
 #[cfg(test)]
 mod tests {
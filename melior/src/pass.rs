@@ -4,22 +4,44 @@ pub mod r#async;
 pub mod conversion;
 pub mod external;
 pub mod gpu;
+mod instrumentation;
 pub mod linalg;
 mod manager;
 mod operation_manager;
+mod options;
+mod pipeline;
+mod registry;
 pub mod sparse_tensor;
+mod statistic;
 pub mod transform;
 
+use self::pipeline::{parse_elements, Element};
+pub(crate) use self::registry::parse_pass_pipeline as parse_external_pass_pipeline;
 pub use self::{
     external::{create_external, ExternalPass, RunExternalPass},
+    instrumentation::{PassInstrumentation, PassTiming, TimingReport},
     manager::PassManager,
     operation_manager::OperationPassManager,
+    options::PassOptions,
+    registry::{register_external_pass, registered_external_passes},
+    statistic::Statistic,
 };
-use mlir_sys::MlirPass;
+use crate::{context::Context, string_ref::StringRef};
+use mlir_sys::{mlirPassManagerCreateOnOperation, MlirPass};
+use std::sync::OnceLock;
+
+thread_local! {
+    // A context for pass managers that print arguments of native passes.
+    static ARGUMENT_CONTEXT: Context = Context::new();
+}
 
 /// A pass.
 pub struct Pass {
     raw: MlirPass,
+    name: Option<String>,
+    argument: OnceLock<Option<String>>,
+    create_raw: Option<unsafe extern "C" fn() -> MlirPass>,
+    statistics: Vec<Statistic>,
 }
 
 impl Pass {
@@ -30,7 +52,8 @@ impl Pass {
     /// A raw function must be valid.
     pub unsafe fn from_raw_fn(create_raw: unsafe extern "C" fn() -> MlirPass) -> Self {
         Self {
-            raw: unsafe { create_raw() },
+            create_raw: Some(create_raw),
+            ..Self::from_raw(unsafe { create_raw() })
         }
     }
 
@@ -40,7 +63,37 @@ impl Pass {
     ///
     /// A raw object must be valid.
     pub const unsafe fn from_raw(raw: MlirPass) -> Self {
-        Self { raw }
+        Self {
+            raw,
+            name: None,
+            argument: OnceLock::new(),
+            create_raw: None,
+            statistics: Vec::new(),
+        }
+    }
+
+    /// Returns a name.
+    ///
+    /// The MLIR C API does not expose names of passes. So this returns `None`
+    /// for passes other than external ones.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns an argument used to reference a pass in a textual pass pipeline,
+    /// such as `canonicalize`.
+    ///
+    /// The MLIR C API does not expose arguments of passes either. For a native
+    /// pass, this adds another instance of the pass to a temporary pass manager
+    /// and prints it on the first call. It returns `None` for passes created by
+    /// [`from_raw`](Self::from_raw).
+    pub fn argument(&self) -> Option<&str> {
+        self.argument
+            .get_or_init(|| {
+                self.create_raw
+                    .and_then(|create_raw| unsafe { Self::print_argument(create_raw) })
+            })
+            .as_deref()
     }
 
     /// Converts a pass into a raw object.
@@ -52,4 +105,72 @@ impl Pass {
     pub unsafe fn __private_from_raw_fn(create_raw: unsafe extern "C" fn() -> MlirPass) -> Self {
         Self::from_raw_fn(create_raw)
     }
+
+    unsafe fn print_argument(create_raw: unsafe extern "C" fn() -> MlirPass) -> Option<String> {
+        ARGUMENT_CONTEXT.with(|context| {
+            // An op-agnostic pass manager accepts passes anchored on any operations.
+            let manager = unsafe {
+                PassManager::from_raw(mlirPassManagerCreateOnOperation(
+                    context.to_raw(),
+                    StringRef::new("any").to_raw(),
+                ))
+            };
+            let manager = manager.as_operation_pass_manager();
+            manager.add_raw_pass(unsafe { Self::from_raw(create_raw()) });
+            let pipeline = manager.to_pipeline_string().ok()?;
+
+            match parse_elements(&pipeline)?.as_slice() {
+                [Element::Nested { elements, .. }] => match elements.as_slice() {
+                    [Element::Pass { name, .. }] => Some(name.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{r#type::TypeId, OperationRef},
+        pass::{conversion, transform},
+    };
+
+    #[test]
+    fn native_pass() {
+        let pass = transform::create_canonicalizer();
+
+        assert_eq!(pass.name(), None);
+        assert_eq!(pass.argument(), Some("canonicalize"));
+    }
+
+    #[test]
+    fn native_pass_with_anchor() {
+        let pass = conversion::create_func_to_llvm();
+
+        assert_eq!(pass.argument(), Some("convert-func-to-llvm"));
+    }
+
+    #[test]
+    fn external_pass() {
+        #[repr(align(8))]
+        struct PassId;
+
+        static PASS_ID: PassId = PassId;
+
+        let pass = create_external(
+            |_: OperationRef, _: ExternalPass| {},
+            TypeId::create(&PASS_ID),
+            "TestPass",
+            "test-pass",
+            "A test pass",
+            "",
+            &[],
+        );
+
+        assert_eq!(pass.name(), Some("TestPass"));
+        assert_eq!(pass.argument(), Some("test-pass"));
+    }
 }
@@ -1,16 +1,23 @@
 //! External passes
 
-use super::Pass;
+use super::{Pass, PassOptions, Statistic};
 use crate::{
     dialect::DialectHandle,
-    ir::{r#type::TypeId, OperationRef},
+    ir::{operation::OperationLike, r#type::TypeId, Location, OperationRef},
+    logical_result::LogicalResult,
     ContextRef, StringRef,
 };
 use mlir_sys::{
-    mlirCreateExternalPass, mlirExternalPassSignalFailure, MlirContext, MlirExternalPass,
-    MlirExternalPassCallbacks, MlirLogicalResult, MlirOperation,
+    mlirCreateExternalPass, mlirEmitError, mlirExternalPassSignalFailure, MlirContext,
+    MlirExternalPass, MlirExternalPassCallbacks, MlirLogicalResult, MlirOperation,
+};
+use std::{
+    error::Error,
+    ffi::{c_void, CString},
+    marker::PhantomData,
+    mem::transmute,
+    ptr::drop_in_place,
 };
-use std::{ffi::c_void, marker::PhantomData, mem::transmute, ptr::drop_in_place};
 
 #[derive(Clone, Copy, Debug)]
 pub struct ExternalPass<'a> {
@@ -59,11 +66,17 @@ unsafe extern "C" fn callback_initialize<'a, T: RunExternalPass<'a>>(
     context: MlirContext,
     pass: *mut T,
 ) -> MlirLogicalResult {
-    pass.as_mut()
+    let context = ContextRef::from_raw(context);
+    let result = pass
+        .as_mut()
         .expect("pass should be valid when called")
-        .initialize(ContextRef::from_raw(context));
+        .initialize(context);
+
+    if let Err(error) = &result {
+        emit_error(Location::unknown(context.to_ref()), &error.to_string());
+    }
 
-    MlirLogicalResult { value: 1 }
+    LogicalResult::from(result.is_ok()).to_raw()
 }
 
 unsafe extern "C" fn callback_run<'a, T: RunExternalPass<'a>>(
@@ -71,12 +84,28 @@ unsafe extern "C" fn callback_run<'a, T: RunExternalPass<'a>>(
     mlir_pass: MlirExternalPass,
     pass: *mut T,
 ) {
-    pass.as_mut()
-        .expect("pass should be valid when called")
-        .run(
-            OperationRef::from_raw(operation),
-            ExternalPass::from_raw(mlir_pass),
-        )
+    let pass = pass.as_mut().expect("pass should be valid when called");
+    let operation = OperationRef::from_raw(operation);
+    let mlir_pass = ExternalPass::from_raw(mlir_pass);
+
+    // The C API does not expose `canScheduleOn` of external passes, so we check
+    // it on every run instead of on pipeline construction.
+    if pass.can_schedule_on(operation) {
+        pass.run(operation, mlir_pass)
+    } else {
+        emit_error(
+            operation.location(),
+            &format!(
+                "unable to schedule external pass on operation {}",
+                operation
+                    .name()
+                    .as_string_ref()
+                    .as_str()
+                    .unwrap_or_default()
+            ),
+        );
+        mlir_pass.signal_failure();
+    }
 }
 
 unsafe extern "C" fn callback_clone<'a, T: RunExternalPass<'a>>(pass: *mut T) -> *mut T {
@@ -87,6 +116,12 @@ unsafe extern "C" fn callback_clone<'a, T: RunExternalPass<'a>>(pass: *mut T) ->
     ))
 }
 
+unsafe fn emit_error(location: Location, message: &str) {
+    let message = CString::new(message.replace('\0', "")).expect("valid C string");
+
+    mlirEmitError(location.to_raw(), message.as_ptr())
+}
+
 /// A trait for MLIR passes written in Rust.
 ///
 /// This trait is implemented for any type that implements `FnMut`,
@@ -102,17 +137,22 @@ unsafe extern "C" fn callback_clone<'a, T: RunExternalPass<'a>>(pass: *mut T) ->
 ///     pass::{ExternalPass, RunExternalPass},
 ///     ContextRef,
 /// };
+/// use std::error::Error;
 ///
 /// #[derive(Clone, Debug)]
 /// struct ExamplePass;
 ///
 /// impl<'c> RunExternalPass<'c> for ExamplePass {
+///     type Options = ();
+///
 ///     fn construct(&mut self) {
 ///         println!("Constructed pass!");
 ///     }
 ///
-///     fn initialize(&mut self, context: ContextRef<'c>) {
+///     fn initialize(&mut self, context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
 ///         println!("Initialize called!");
+///
+///         Ok(())
 ///     }
 ///
 ///     fn run(&mut self, operation: OperationRef<'c, '_>, _pass: ExternalPass<'_>) {
@@ -121,14 +161,52 @@ unsafe extern "C" fn callback_clone<'a, T: RunExternalPass<'a>>(pass: *mut T) ->
 /// }
 /// ```
 pub trait RunExternalPass<'c>: Sized + Clone {
+    /// Options of a pass.
+    ///
+    /// Use `()` for passes without options.
+    type Options: PassOptions;
+
     fn construct(&mut self) {}
     fn destruct(&mut self) {}
-    fn initialize(&mut self, context: ContextRef<'c>);
+
+    /// Initializes a pass.
+    ///
+    /// If it returns an error, the error is emitted as a diagnostic and the
+    /// pass manager fails to run.
+    fn initialize(&mut self, context: ContextRef<'c>) -> Result<(), Box<dyn Error>>;
+
+    /// Returns `true` if a pass can run on an operation.
+    ///
+    /// If it returns `false`, the pass fails with a diagnostic instead of
+    /// running.
+    fn can_schedule_on(&self, _operation: OperationRef<'c, '_>) -> bool {
+        true
+    }
+
     fn run(&mut self, operation: OperationRef<'c, '_>, pass: ExternalPass<'_>);
+
+    /// Sets options of a pass.
+    ///
+    /// It is meant to be called with options parsed from a textual pass
+    /// pipeline by a factory given to
+    /// [`register_external_pass`](super::register_external_pass).
+    fn set_options(&mut self, _options: Self::Options) {}
+
+    /// Returns statistics of a pass.
+    ///
+    /// They are collected by a pass manager that a pass is added to and
+    /// reported by [`PassManager::statistics_report`](super::PassManager::statistics_report).
+    fn statistics(&self) -> Vec<Statistic> {
+        vec![]
+    }
 }
 
 impl<'c, F: FnMut(OperationRef<'c, '_>, ExternalPass<'_>) + Clone> RunExternalPass<'c> for F {
-    fn initialize(&mut self, _context: ContextRef<'c>) {}
+    type Options = ();
+
+    fn initialize(&mut self, _context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
 
     fn run(&mut self, operation: OperationRef<'c, '_>, pass: ExternalPass<'_>) {
         self(operation, pass)
@@ -175,7 +253,9 @@ pub fn create_external<'c, T: RunExternalPass<'c>>(
     op_name: &str,
     dependent_dialects: &[DialectHandle],
 ) -> Pass {
-    unsafe {
+    let statistics = pass.statistics();
+
+    let mut pass = unsafe {
         Pass::from_raw(mlirCreateExternalPass(
             pass_id.to_raw(),
             StringRef::new(name).to_raw(),
@@ -206,7 +286,12 @@ pub fn create_external<'c, T: RunExternalPass<'c>>(
             },
             Box::into_raw(Box::new(pass)) as _,
         ))
-    }
+    };
+
+    pass.name = Some(name.into());
+    pass.argument = Some(argument.into()).into();
+    pass.statistics = statistics;
+    pass
 }
 
 #[cfg(test)]
@@ -225,6 +310,8 @@ mod tests {
         test::create_test_context,
         Context,
     };
+    use indoc::indoc;
+    use std::cell::RefCell;
 
     #[repr(align(8))]
     struct PassId;
@@ -262,6 +349,8 @@ mod tests {
         }
 
         impl<'c> RunExternalPass<'c> for TestPass<'c> {
+            type Options = ();
+
             fn construct(&mut self) {
                 assert_eq!(self.value, 10);
             }
@@ -270,9 +359,11 @@ mod tests {
                 assert_eq!(self.value, 30);
             }
 
-            fn initialize(&mut self, _context: ContextRef<'c>) {
+            fn initialize(&mut self, _context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
                 assert_eq!(self.value, 10);
                 self.value = 20;
+
+                Ok(())
             }
 
             fn run(&mut self, operation: OperationRef<'c, '_>, _pass: ExternalPass<'_>) {
@@ -354,4 +445,185 @@ mod tests {
         ));
         assert!(pass_manager.run(&mut module).is_err());
     }
+
+    #[test]
+    fn external_pass_initialize_failure() {
+        static TEST_PASS: PassId = PassId;
+
+        #[derive(Clone, Debug)]
+        struct TestPass;
+
+        impl<'c> RunExternalPass<'c> for TestPass {
+            type Options = ();
+
+            fn initialize(&mut self, _context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
+                Err("missing configuration".into())
+            }
+
+            fn run(&mut self, _operation: OperationRef<'c, '_>, _pass: ExternalPass<'_>) {
+                unreachable!()
+            }
+        }
+
+        let context = create_test_context();
+        let messages = RefCell::new(vec![]);
+        context.attach_diagnostic_handler(|diagnostic| {
+            messages.borrow_mut().push(diagnostic.to_string());
+            true
+        });
+
+        let mut module = create_module(&context);
+        let pass_manager = PassManager::new(&context);
+
+        pass_manager.add_pass(create_external(
+            TestPass,
+            TypeId::create(&TEST_PASS),
+            "test pass",
+            "test argument",
+            "a test pass",
+            "",
+            &[],
+        ));
+
+        assert!(pass_manager.run(&mut module).is_err());
+        assert_eq!(messages.borrow().as_slice(), ["missing configuration"]);
+    }
+
+    #[test]
+    fn external_pass_can_schedule_on() {
+        static TEST_PASS: PassId = PassId;
+
+        #[derive(Clone, Debug)]
+        struct TestPass;
+
+        impl<'c> RunExternalPass<'c> for TestPass {
+            type Options = ();
+
+            fn initialize(&mut self, _context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
+                Ok(())
+            }
+
+            fn can_schedule_on(&self, operation: OperationRef<'c, '_>) -> bool {
+                operation.name().as_string_ref().as_str() == Ok("func.func")
+            }
+
+            fn run(&mut self, _operation: OperationRef<'c, '_>, _pass: ExternalPass<'_>) {}
+        }
+
+        let context = create_test_context();
+        let messages = RefCell::new(vec![]);
+        context.attach_diagnostic_handler(|diagnostic| {
+            messages.borrow_mut().push(diagnostic.to_string());
+            true
+        });
+
+        let mut module = create_module(&context);
+        let pass_manager = PassManager::new(&context);
+
+        pass_manager.add_pass(create_external(
+            TestPass,
+            TypeId::create(&TEST_PASS),
+            "test pass",
+            "test argument",
+            "a test pass",
+            "",
+            &[],
+        ));
+
+        assert!(pass_manager.run(&mut module).is_err());
+        assert_eq!(
+            messages.borrow().as_slice(),
+            ["unable to schedule external pass on operation builtin.module"]
+        );
+
+        let pass_manager = PassManager::new(&context);
+
+        pass_manager
+            .nested_under("func.func")
+            .add_pass(create_external(
+                TestPass,
+                TypeId::create(&TEST_PASS),
+                "test pass",
+                "test argument",
+                "a test pass",
+                "",
+                &[],
+            ));
+
+        assert_eq!(pass_manager.run(&mut module), Ok(()));
+    }
+
+    #[test]
+    fn external_pass_options_and_statistics() {
+        static TEST_PASS: PassId = PassId;
+
+        #[derive(Default, crate::PassOptions)]
+        struct TestOptions {
+            step: u64,
+        }
+
+        #[derive(Clone, Debug)]
+        struct TestPass {
+            step: u64,
+            operation_count: Statistic,
+        }
+
+        impl<'c> RunExternalPass<'c> for TestPass {
+            type Options = TestOptions;
+
+            fn initialize(&mut self, _context: ContextRef<'c>) -> Result<(), Box<dyn Error>> {
+                Ok(())
+            }
+
+            fn run(&mut self, _operation: OperationRef<'c, '_>, _pass: ExternalPass<'_>) {
+                self.operation_count.add(self.step);
+            }
+
+            fn set_options(&mut self, options: Self::Options) {
+                self.step = options.step;
+            }
+
+            fn statistics(&self) -> Vec<Statistic> {
+                vec![self.operation_count.clone()]
+            }
+        }
+
+        let context = create_test_context();
+
+        let mut module = create_module(&context);
+        let pass_manager = PassManager::new(&context);
+
+        let mut pass = TestPass {
+            step: 1,
+            operation_count: Statistic::new("num-operations", "Number of operations"),
+        };
+        pass.set_options(TestOptions::parse("step=2").unwrap());
+
+        pass_manager
+            .nested_under("func.func")
+            .add_pass(create_external(
+                pass,
+                TypeId::create(&TEST_PASS),
+                "TestPass",
+                "test-pass",
+                "a test pass",
+                "func.func",
+                &[],
+            ));
+
+        pass_manager.run(&mut module).unwrap();
+
+        assert_eq!(
+            pass_manager.statistics_report(),
+            indoc!(
+                "
+                ===-------------------------------------------------------------------------===
+                                         ... Pass statistics report ...
+                ===-------------------------------------------------------------------------===
+                TestPass
+                  (S) 2 num-operations - Number of operations"
+            )
+            .trim_start()
+        );
+    }
 }
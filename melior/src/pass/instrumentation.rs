@@ -0,0 +1,287 @@
+use super::{
+    create_external,
+    pipeline::{parse_elements, Element},
+    ExternalPass, OperationPassManager, Pass,
+};
+use crate::ir::{operation::OperationLike, r#type::TypeId, OperationRef};
+use mlir_sys::MlirOperation;
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+const MARKER_ARGUMENT: &str = "melior-instrumentation";
+
+#[repr(align(8))]
+struct PassId;
+
+static MARKER_PASS: PassId = PassId;
+
+/// A pass instrumentation.
+///
+/// Instrumentations are called by pass managers around passes. Passes run on
+/// multiple threads when multi-threading is enabled in a context, so its
+/// methods can be called concurrently.
+///
+/// Passes are identified by their arguments, such as `canonicalize`. Passes
+/// created by [`Pass::from_raw`] have empty names.
+///
+/// [`run_before_analysis`](Self::run_before_analysis) is not supported. The
+/// MLIR C API does not expose analyses to passes nor instrumentations. So it
+/// is never called.
+pub trait PassInstrumentation: Send + Sync {
+    /// Runs before a pass runs on an operation.
+    fn run_before_pass(&self, _pass: &str, _operation: OperationRef) {}
+
+    /// Runs after a pass successfully runs on an operation.
+    fn run_after_pass(&self, _pass: &str, _operation: OperationRef) {}
+
+    /// Runs after a pass fails on an operation.
+    fn run_after_pass_failed(&self, _pass: &str, _operation: OperationRef) {}
+
+    /// Runs before an analysis is computed on an operation.
+    ///
+    /// This is not supported and never called.
+    fn run_before_analysis(&self, _analysis: &str, _operation: OperationRef) {}
+}
+
+/// Instrumentations of a pass manager.
+///
+/// The MLIR C API does not expose pass instrumentations. Instead, marker passes
+/// that call instrumentations are added before and after each pass.
+#[derive(Default)]
+pub(super) struct Instrumentations {
+    instrumentations: RwLock<Vec<Arc<dyn PassInstrumentation>>>,
+    // Passes with operations that have not finished running yet.
+    pending: Mutex<Vec<(String, usize)>>,
+}
+
+impl Instrumentations {
+    pub fn add(&self, instrumentation: Arc<dyn PassInstrumentation>) {
+        self.instrumentations
+            .write()
+            .expect("valid instrumentations")
+            .push(instrumentation);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instrumentations
+            .read()
+            .expect("valid instrumentations")
+            .is_empty()
+    }
+
+    /// Adds passes of a given name with markers to an operation pass manager.
+    pub fn add_passes<T>(
+        self: &Arc<Self>,
+        manager: &OperationPassManager,
+        name: &str,
+        add: impl FnOnce() -> T,
+    ) -> T {
+        let name = Arc::<str>::from(name);
+
+        manager.add_raw_pass(self.create_marker(name.clone(), Self::run_before_pass));
+        let value = add();
+        manager.add_raw_pass(self.create_marker(name, Self::run_after_pass));
+
+        value
+    }
+
+    /// Calls instrumentations for passes that failed.
+    pub fn finish(&self) {
+        for (name, operation) in self.pending.lock().expect("valid pending passes").drain(..) {
+            let operation = unsafe {
+                OperationRef::from_raw(MlirOperation {
+                    ptr: operation as *mut _,
+                })
+            };
+
+            for instrumentation in self.instrumentations().iter() {
+                instrumentation.run_after_pass_failed(&name, operation);
+            }
+        }
+    }
+
+    fn create_marker(self: &Arc<Self>, name: Arc<str>, run: fn(&Self, &str, OperationRef)) -> Pass {
+        let instrumentations = self.clone();
+
+        create_external(
+            move |operation: OperationRef, _: ExternalPass| {
+                run(&instrumentations, &name, operation)
+            },
+            TypeId::create(&MARKER_PASS),
+            MARKER_ARGUMENT,
+            MARKER_ARGUMENT,
+            "A marker pass for pass instrumentations",
+            "",
+            &[],
+        )
+    }
+
+    fn run_before_pass(&self, name: &str, operation: OperationRef) {
+        self.pending
+            .lock()
+            .expect("valid pending passes")
+            .push((name.into(), operation.to_raw().ptr as usize));
+
+        for instrumentation in self.instrumentations().iter() {
+            instrumentation.run_before_pass(name, operation);
+        }
+    }
+
+    fn run_after_pass(&self, name: &str, operation: OperationRef) {
+        let key = (name.into(), operation.to_raw().ptr as usize);
+        self.pending
+            .lock()
+            .expect("valid pending passes")
+            .retain(|pending| pending != &key);
+
+        for instrumentation in self.instrumentations().iter() {
+            instrumentation.run_after_pass(name, operation);
+        }
+    }
+
+    fn instrumentations(&self) -> Vec<Arc<dyn PassInstrumentation>> {
+        self.instrumentations
+            .read()
+            .expect("valid instrumentations")
+            .clone()
+    }
+}
+
+impl Debug for Instrumentations {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Instrumentations")
+            .field("count", &self.instrumentations().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Removes marker passes from a textual pass pipeline.
+pub(super) fn remove_markers(pipeline: &str) -> Option<String> {
+    if !pipeline.contains(MARKER_ARGUMENT) {
+        return Some(pipeline.into());
+    }
+
+    Some(format_elements(&parse_elements(pipeline)?))
+}
+
+fn format_elements(elements: &[Element]) -> String {
+    elements
+        .iter()
+        .filter_map(|element| match element {
+            Element::Pass {
+                name: MARKER_ARGUMENT,
+                ..
+            } => None,
+            Element::Pass { source, .. } => Some(source.to_string()),
+            Element::Nested { name, elements, .. } => {
+                Some(format!("{name}({})", format_elements(elements)))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A wall time of a pass run on an operation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PassTiming {
+    pass: String,
+    operation: String,
+    depth: usize,
+    wall_time: Duration,
+}
+
+impl PassTiming {
+    /// Returns a pass name.
+    pub fn pass(&self) -> &str {
+        &self.pass
+    }
+
+    /// Returns a name of an operation the pass ran on.
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// Returns a nesting depth of the operation, which is `0` for top-level
+    /// operations.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns a wall time.
+    pub const fn wall_time(&self) -> Duration {
+        self.wall_time
+    }
+}
+
+/// A timing report of passes.
+///
+/// It is created by [`PassManager::enable_timing`](super::PassManager::enable_timing)
+/// and filled with timings of passes as they finish. Clones of a report share
+/// the same timings.
+#[derive(Clone, Debug, Default)]
+pub struct TimingReport {
+    timings: Arc<Mutex<Vec<PassTiming>>>,
+    starts: Arc<Mutex<HashMap<(String, usize), Instant>>>,
+}
+
+impl TimingReport {
+    /// Returns timings of passes in order of their completion.
+    pub fn timings(&self) -> Vec<PassTiming> {
+        self.timings.lock().expect("valid timings").clone()
+    }
+
+    fn finish(&self, pass: &str, operation: OperationRef) {
+        let Some(start) = self
+            .starts
+            .lock()
+            .expect("valid timings")
+            .remove(&(pass.into(), operation.to_raw().ptr as usize))
+        else {
+            return;
+        };
+
+        self.timings
+            .lock()
+            .expect("valid timings")
+            .push(PassTiming {
+                pass: pass.into(),
+                operation: operation
+                    .name()
+                    .as_string_ref()
+                    .as_str()
+                    .unwrap_or_default()
+                    .into(),
+                depth: depth(&operation),
+                wall_time: start.elapsed(),
+            });
+    }
+}
+
+impl PassInstrumentation for TimingReport {
+    fn run_before_pass(&self, pass: &str, operation: OperationRef) {
+        self.starts.lock().expect("valid timings").insert(
+            (pass.into(), operation.to_raw().ptr as usize),
+            Instant::now(),
+        );
+    }
+
+    fn run_after_pass(&self, pass: &str, operation: OperationRef) {
+        self.finish(pass, operation);
+    }
+
+    fn run_after_pass_failed(&self, pass: &str, operation: OperationRef) {
+        self.finish(pass, operation);
+    }
+}
+
+fn depth<'c>(operation: &impl OperationLike<'c, 'c>) -> usize {
+    operation
+        .parent_operation()
+        .map(|operation| depth(&operation) + 1)
+        .unwrap_or_default()
+}
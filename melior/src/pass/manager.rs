@@ -3,31 +3,33 @@ use crate::{
     context::Context,
     ir::{operation::OperationPrintingFlags, Module},
     logical_result::LogicalResult,
-    pass::Pass,
+    pass::{
+        instrumentation::Instrumentations, statistic::PassStatistics, Pass, PassInstrumentation,
+        TimingReport,
+    },
     string_ref::StringRef,
     Error,
 };
 use mlir_sys::{
-    mlirPassManagerAddOwnedPass, mlirPassManagerCreate, mlirPassManagerDestroy,
-    mlirPassManagerEnableIRPrinting, mlirPassManagerEnableVerifier,
-    mlirPassManagerGetAsOpPassManager, mlirPassManagerGetNestedUnder, mlirPassManagerRunOnOp,
-    MlirPassManager,
+    mlirPassManagerCreate, mlirPassManagerDestroy, mlirPassManagerEnableIRPrinting,
+    mlirPassManagerEnableVerifier, mlirPassManagerGetAsOpPassManager,
+    mlirPassManagerGetNestedUnder, mlirPassManagerRunOnOp, MlirPassManager,
 };
-use std::{marker::PhantomData, mem::forget, path::PathBuf};
+use std::{cell::RefCell, fmt::Write, marker::PhantomData, mem::forget, path::PathBuf, sync::Arc};
 
 /// A pass manager.
+#[derive(Debug)]
 pub struct PassManager<'c> {
     raw: MlirPassManager,
+    pub(super) statistics: RefCell<PassStatistics>,
+    pub(super) instrumentations: Arc<Instrumentations>,
     _context: PhantomData<&'c Context>,
 }
 
 impl<'c> PassManager<'c> {
     /// Creates a pass manager.
     pub fn new(context: &Context) -> Self {
-        Self {
-            raw: unsafe { mlirPassManagerCreate(context.to_raw()) },
-            _context: Default::default(),
-        }
+        unsafe { Self::from_raw(mlirPassManagerCreate(context.to_raw())) }
     }
 
     /// Returns an operation pass manager for nested operations corresponding to
@@ -38,11 +40,51 @@ impl<'c> PassManager<'c> {
         unsafe {
             OperationPassManager::from_raw(mlirPassManagerGetNestedUnder(self.raw, name.to_raw()))
         }
+        .with_manager(self)
     }
 
     /// Adds a pass.
     pub fn add_pass(&self, pass: Pass) {
-        unsafe { mlirPassManagerAddOwnedPass(self.raw, pass.to_raw()) }
+        self.as_operation_pass_manager().add_pass(pass)
+    }
+
+    /// Adds an instrumentation.
+    ///
+    /// Instrumentations must be added before any passes. Otherwise, it returns
+    /// an error as passes added before are not instrumented.
+    ///
+    /// Because the MLIR C API does not expose pass instrumentations, marker
+    /// passes are added around instrumented passes. They are omitted from
+    /// [`OperationPassManager::to_pipeline_string`] and
+    /// [`OperationPassManager::passes`].
+    pub fn add_instrumentation(
+        &self,
+        instrumentation: impl PassInstrumentation + 'static,
+    ) -> Result<(), Error> {
+        let manager = self.as_operation_pass_manager();
+
+        if manager.pass_count()? > 0 {
+            return Err(Error::InstrumentationAfterPasses(
+                manager.to_pipeline_string()?,
+            ));
+        }
+
+        self.instrumentations.add(Arc::new(instrumentation));
+
+        Ok(())
+    }
+
+    /// Enables timing of passes.
+    ///
+    /// It returns a report that is filled with wall times of passes as they
+    /// run. Like [`add_instrumentation`](Self::add_instrumentation), it must
+    /// be called before any passes are added.
+    pub fn enable_timing(&self) -> Result<TimingReport, Error> {
+        let report = TimingReport::default();
+
+        self.add_instrumentation(report.clone())?;
+
+        Ok(report)
     }
 
     /// Enables a verifier.
@@ -50,6 +92,31 @@ impl<'c> PassManager<'c> {
         unsafe { mlirPassManagerEnableVerifier(self.raw, enabled) }
     }
 
+    /// Returns a statistics report of external passes in the same format as
+    /// `--mlir-pass-statistics`.
+    ///
+    /// Only statistics of passes added through this pass manager or operation
+    /// pass managers derived from it are reported. Statistics accumulate over
+    /// runs.
+    pub fn statistics_report(&self) -> String {
+        let mut report = [
+            "===-------------------------------------------------------------------------===",
+            "                         ... Pass statistics report ...",
+            "===-------------------------------------------------------------------------===",
+        ]
+        .join("\n");
+
+        for (name, statistics) in self.statistics.borrow().iter() {
+            write!(report, "\n{name}").expect("infallible write");
+
+            for statistic in statistics {
+                write!(report, "\n  {statistic}").expect("infallible write");
+            }
+        }
+
+        report
+    }
+
     /// Enable IR printing.
     /// The treePrintingPath argument is an optional path to a directory
     /// where the dumps will be produced. If it isn't provided then dumps
@@ -75,6 +142,8 @@ impl<'c> PassManager<'c> {
             mlirPassManagerRunOnOp(self.raw, module.as_operation().to_raw())
         });
 
+        self.instrumentations.finish();
+
         if result.is_success() {
             Ok(())
         } else {
@@ -85,6 +154,7 @@ impl<'c> PassManager<'c> {
     /// Converts a pass manager to an operation pass manager.
     pub fn as_operation_pass_manager(&self) -> OperationPassManager<'c, '_> {
         unsafe { OperationPassManager::from_raw(mlirPassManagerGetAsOpPassManager(self.raw)) }
+            .with_manager(self)
     }
 
     /// Creates a PassManager from the given raw pointer.
@@ -94,6 +164,8 @@ impl<'c> PassManager<'c> {
     pub unsafe fn from_raw(raw: MlirPassManager) -> Self {
         Self {
             raw,
+            statistics: Default::default(),
+            instrumentations: Default::default(),
             _context: Default::default(),
         }
     }
@@ -132,13 +204,14 @@ pub struct PassIrPrintingOptions {
 mod tests {
     use super::*;
     use crate::{
-        ir::{Location, Module},
-        pass::{self, transform::register_print_op_stats},
+        ir::{operation::OperationLike, r#type::TypeId, Location, Module, OperationRef},
+        pass::{self, create_external, transform::register_print_op_stats, ExternalPass},
         test::create_test_context,
         utility::parse_pass_pipeline,
     };
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
 
     #[test]
     fn new() {
@@ -290,4 +363,224 @@ mod tests {
             func.func(print-op-stats{json=false}))"
         );
     }
+
+    #[test]
+    fn passes() {
+        let context = create_test_context();
+        let manager = PassManager::new(&context);
+        let function_manager = manager.nested_under("func.func");
+
+        manager.add_pass(pass::transform::create_canonicalizer());
+        function_manager.add_pass(pass::transform::create_print_op_stats());
+
+        let manager = manager.as_operation_pass_manager();
+
+        assert_eq!(manager.pass_count(), Ok(2));
+        assert_eq!(
+            manager.passes().unwrap()[0],
+            "func.func(print-op-stats{json=false})"
+        );
+        assert_eq!(
+            function_manager.passes(),
+            Ok(vec!["print-op-stats{json=false}".into()])
+        );
+        assert_eq!(
+            PassManager::new(&context)
+                .as_operation_pass_manager()
+                .pass_count(),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn round_trip_pass_pipeline() {
+        let context = create_test_context();
+        let manager = PassManager::new(&context);
+
+        manager
+            .nested_under("func.func")
+            .add_pass(pass::transform::create_print_op_stats());
+        manager.add_pass(pass::transform::create_cse());
+
+        let pipeline = manager
+            .as_operation_pass_manager()
+            .to_pipeline_string()
+            .unwrap();
+        let other_manager = PassManager::new(&context);
+
+        register_print_op_stats();
+        pass::transform::register_cse();
+
+        parse_pass_pipeline(other_manager.as_operation_pass_manager(), &pipeline).unwrap();
+
+        assert_eq!(
+            other_manager
+                .as_operation_pass_manager()
+                .to_pipeline_string(),
+            Ok(pipeline)
+        );
+    }
+
+    #[derive(Clone, Default)]
+    struct TestInstrumentation {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl TestInstrumentation {
+        fn record(&self, event: &str, pass: &str, operation: OperationRef) {
+            self.events.lock().unwrap().push(format!(
+                "{event} {pass} {}",
+                operation.name().as_string_ref().as_str().unwrap()
+            ));
+        }
+    }
+
+    impl PassInstrumentation for TestInstrumentation {
+        fn run_before_pass(&self, pass: &str, operation: OperationRef) {
+            self.record("before", pass, operation);
+        }
+
+        fn run_after_pass(&self, pass: &str, operation: OperationRef) {
+            self.record("after", pass, operation);
+        }
+
+        fn run_after_pass_failed(&self, pass: &str, operation: OperationRef) {
+            self.record("failed", pass, operation);
+        }
+    }
+
+    #[test]
+    fn add_instrumentation() {
+        #[repr(align(8))]
+        struct PassId;
+
+        static TEST_PASS: PassId = PassId;
+
+        let context = create_test_context();
+        let mut module = Module::parse(&context, "func.func @foo() { return }").unwrap();
+        let manager = PassManager::new(&context);
+        let instrumentation = TestInstrumentation::default();
+
+        manager
+            .add_instrumentation(instrumentation.clone())
+            .unwrap();
+        manager
+            .nested_under("func.func")
+            .add_pass(pass::transform::create_cse());
+        manager.add_pass(create_external(
+            |_: OperationRef, pass: ExternalPass| pass.signal_failure(),
+            TypeId::create(&TEST_PASS),
+            "test pass",
+            "test-pass",
+            "a test pass",
+            "",
+            &[],
+        ));
+
+        assert_eq!(manager.run(&mut module), Err(Error::RunPass));
+        assert_eq!(
+            *instrumentation.events.lock().unwrap(),
+            [
+                "before cse func.func",
+                "after cse func.func",
+                "before test-pass builtin.module",
+                "failed test-pass builtin.module",
+            ]
+        );
+    }
+
+    #[test]
+    fn add_instrumentation_after_passes() {
+        let context = create_test_context();
+        let manager = PassManager::new(&context);
+
+        manager.add_pass(pass::transform::create_cse());
+
+        assert_eq!(
+            manager.add_instrumentation(TestInstrumentation::default()),
+            Err(Error::InstrumentationAfterPasses("any(cse)".into()))
+        );
+    }
+
+    #[test]
+    fn add_instrumentation_with_parsed_pass_pipeline() {
+        let context = create_test_context();
+        let mut module = Module::parse(&context, "func.func @foo() { return }").unwrap();
+        let manager = PassManager::new(&context);
+        let instrumentation = TestInstrumentation::default();
+
+        manager
+            .add_instrumentation(instrumentation.clone())
+            .unwrap();
+
+        pass::transform::register_cse();
+
+        parse_pass_pipeline(
+            manager.as_operation_pass_manager(),
+            "builtin.module(func.func(cse),cse)",
+        )
+        .unwrap();
+
+        assert_eq!(manager.run(&mut module), Ok(()));
+        assert_eq!(
+            *instrumentation.events.lock().unwrap(),
+            [
+                "before cse func.func",
+                "after cse func.func",
+                "before cse builtin.module",
+                "after cse builtin.module",
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip_instrumented_pass_pipeline() {
+        let context = create_test_context();
+        let manager = PassManager::new(&context);
+
+        manager
+            .add_instrumentation(TestInstrumentation::default())
+            .unwrap();
+        manager
+            .nested_under("func.func")
+            .add_pass(pass::transform::create_cse());
+        manager.add_pass(pass::transform::create_cse());
+
+        let manager = manager.as_operation_pass_manager();
+
+        assert_eq!(
+            manager.to_pipeline_string(),
+            Ok("any(func.func(cse),cse)".into())
+        );
+        assert_eq!(manager.to_string(), "any(func.func(cse),cse)");
+        assert_eq!(manager.pass_count(), Ok(2));
+    }
+
+    #[test]
+    fn enable_timing() {
+        let context = create_test_context();
+        let mut module = Module::parse(&context, "func.func @foo() { return }").unwrap();
+        let manager = PassManager::new(&context);
+        let report = manager.enable_timing().unwrap();
+
+        manager
+            .nested_under("func.func")
+            .add_pass(pass::transform::create_cse());
+        manager.add_pass(pass::transform::create_canonicalizer());
+
+        manager.run(&mut module).unwrap();
+
+        let timings = report.timings();
+
+        assert_eq!(
+            timings
+                .iter()
+                .map(|timing| (timing.pass(), timing.operation(), timing.depth()))
+                .collect::<Vec<_>>(),
+            [
+                ("cse", "func.func", 1),
+                ("canonicalize", "builtin.module", 0)
+            ]
+        );
+    }
 }
@@ -1,36 +1,137 @@
-use super::PassManager;
-use crate::{pass::Pass, string_ref::StringRef};
+use super::{
+    instrumentation::{remove_markers, Instrumentations},
+    pipeline::{parse_elements, Element},
+    PassManager,
+};
+use crate::{pass::Pass, string_ref::StringRef, utility::print_string_callback, Error};
 use mlir_sys::{
     mlirOpPassManagerAddOwnedPass, mlirOpPassManagerGetNestedUnder, mlirPrintPassPipeline,
-    MlirOpPassManager, MlirStringRef,
+    MlirOpPassManager,
 };
 use std::{
-    ffi::c_void,
     fmt::{self, Display, Formatter},
     marker::PhantomData,
+    sync::Arc,
 };
 
 /// An operation pass manager.
 #[derive(Clone, Copy, Debug)]
 pub struct OperationPassManager<'c, 'a> {
     raw: MlirOpPassManager,
+    manager: Option<&'a PassManager<'c>>,
     _parent: PhantomData<&'a PassManager<'c>>,
 }
 
-impl OperationPassManager<'_, '_> {
+impl<'c, 'a> OperationPassManager<'c, 'a> {
     /// Returns an operation pass manager for nested operations corresponding to
     /// a given name.
     pub fn nested_under(&self, name: &str) -> Self {
         let name = StringRef::new(name);
 
-        unsafe { Self::from_raw(mlirOpPassManagerGetNestedUnder(self.raw, name.to_raw())) }
+        Self {
+            manager: self.manager,
+            ..unsafe { Self::from_raw(mlirOpPassManagerGetNestedUnder(self.raw, name.to_raw())) }
+        }
     }
 
     /// Adds a pass.
+    ///
+    /// Statistics of the pass are collected and the pass is instrumented by
+    /// the parent pass manager. An operation pass manager created by
+    /// [`from_raw`](Self::from_raw) has no parent pass manager, so it does
+    /// neither.
     pub fn add_pass(&self, pass: Pass) {
+        let Some(manager) = self.manager else {
+            return self.add_raw_pass(pass);
+        };
+
+        if !pass.statistics.is_empty() {
+            manager.statistics.borrow_mut().push((
+                pass.name.clone().unwrap_or_default(),
+                pass.statistics.clone(),
+            ));
+        }
+
+        if let Some(instrumentations) = self.instrumentations() {
+            let name = pass.argument().unwrap_or_default().to_owned();
+
+            instrumentations.add_passes(self, &name, || self.add_raw_pass(pass));
+        } else {
+            self.add_raw_pass(pass);
+        }
+    }
+
+    pub(super) fn with_manager(self, manager: &'a PassManager<'c>) -> Self {
+        Self {
+            manager: Some(manager),
+            ..self
+        }
+    }
+
+    /// Adds a pass without collecting its statistics or instrumenting it.
+    pub(super) fn add_raw_pass(&self, pass: Pass) {
         unsafe { mlirOpPassManagerAddOwnedPass(self.raw, pass.to_raw()) }
     }
 
+    /// Returns instrumentations of the parent pass manager if any.
+    pub(super) fn instrumentations(&self) -> Option<&'a Arc<Instrumentations>> {
+        self.manager
+            .map(|manager| &manager.instrumentations)
+            .filter(|instrumentations| !instrumentations.is_empty())
+    }
+
+    /// Returns a pass pipeline in the textual form.
+    ///
+    /// The pipeline can be parsed back with
+    /// [`parse_pass_pipeline`](crate::utility::parse_pass_pipeline). Marker
+    /// passes of instrumentations are omitted.
+    pub fn to_pipeline_string(&self) -> Result<String, Error> {
+        let mut data = (String::new(), Ok::<_, Error>(()));
+
+        unsafe {
+            mlirPrintPassPipeline(
+                self.raw,
+                Some(print_string_callback),
+                &mut data as *mut _ as *mut _,
+            );
+        }
+
+        data.1?;
+
+        match remove_markers(&data.0) {
+            Some(pipeline) => Ok(pipeline),
+            None => Err(Error::ParsePassPipeline(data.0)),
+        }
+    }
+
+    /// Returns passes in the textual form.
+    ///
+    /// Passes of nested operation pass managers are grouped into single
+    /// elements anchored on their operations, as in `func.func(cse)`.
+    ///
+    /// The MLIR C API does not expose passes of pass managers. So this prints
+    /// a pass pipeline with [`to_pipeline_string`](Self::to_pipeline_string)
+    /// and parses it again on every call.
+    pub fn passes(&self) -> Result<Vec<String>, Error> {
+        let pipeline = self.to_pipeline_string()?;
+
+        match parse_elements(&pipeline).as_deref() {
+            Some([Element::Nested { elements, .. }]) => Ok(elements
+                .iter()
+                .map(|element| element.source().into())
+                .collect()),
+            _ => Err(Error::ParsePassPipeline(pipeline)),
+        }
+    }
+
+    /// Returns a number of passes.
+    ///
+    /// Like [`passes`](Self::passes), it prints and parses a pass pipeline on
+    /// every call.
+    pub fn pass_count(&self) -> Result<usize, Error> {
+        Ok(self.passes()?.len())
+    }
+
     /// Converts an operation pass manager into a raw object.
     pub const fn to_raw(self) -> MlirOpPassManager {
         self.raw
@@ -38,12 +139,16 @@ impl OperationPassManager<'_, '_> {
 
     /// Creates an operation pass manager from a raw object.
     ///
+    /// It is not associated with any parent [`PassManager`]. Statistics of
+    /// passes added to it are not collected and they are not instrumented.
+    ///
     /// # Safety
     ///
     /// A raw object must be valid.
     pub unsafe fn from_raw(raw: MlirOpPassManager) -> Self {
         Self {
             raw,
+            manager: None,
             _parent: Default::default(),
         }
     }
@@ -51,29 +156,10 @@ impl OperationPassManager<'_, '_> {
 
 impl Display for OperationPassManager<'_, '_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        let mut data = (formatter, Ok(()));
-
-        unsafe extern "C" fn callback(string: MlirStringRef, data: *mut c_void) {
-            let data = &mut *(data as *mut (&mut Formatter, fmt::Result));
-            let result = (|| -> fmt::Result {
-                write!(
-                    data.0,
-                    "{}",
-                    StringRef::from_raw(string)
-                        .as_str()
-                        .map_err(|_| fmt::Error)?
-                )
-            })();
-
-            if data.1.is_ok() {
-                data.1 = result;
-            }
-        }
-
-        unsafe {
-            mlirPrintPassPipeline(self.raw, Some(callback), &mut data as *mut _ as *mut c_void);
-        }
-
-        data.1
+        write!(
+            formatter,
+            "{}",
+            self.to_pipeline_string().map_err(|_| fmt::Error)?
+        )
     }
 }
@@ -0,0 +1,162 @@
+use crate::Error;
+
+/// Options of a pass.
+///
+/// Options are written in the `name=value` form and separated by spaces, as
+/// in `my-pass{threshold=4 verbose}`. Values can be quoted or enclosed in
+/// braces. Options without values are set to `true`.
+///
+/// It is usually derived with the [`PassOptions`](crate::PassOptions) macro.
+pub trait PassOptions: Default {
+    /// Sets an option by its name.
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), Error>;
+
+    /// Parses options.
+    fn parse(source: &str) -> Result<Self, Error> {
+        let mut options = Self::default();
+
+        for option in split_options(source)? {
+            let (name, value) = option.split_once('=').unwrap_or((option, "true"));
+
+            options.set_option(name, unquote(value))?;
+        }
+
+        Ok(options)
+    }
+}
+
+impl PassOptions for () {
+    fn set_option(&mut self, name: &str, _value: &str) -> Result<(), Error> {
+        Err(Error::ParsePassPipeline(format!(
+            "unknown pass option: {name}"
+        )))
+    }
+}
+
+fn split_options(source: &str) -> Result<Vec<&str>, Error> {
+    let mut options = vec![];
+    let mut start = None;
+    let mut depth = 0usize;
+    let mut quote = None;
+
+    for (index, character) in source.char_indices() {
+        match (quote, character) {
+            (Some(delimiter), _) if character == delimiter => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(character),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| unterminated_options(source))?
+            }
+            (None, _) if character.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    options.push(&source[start..index]);
+                }
+
+                continue;
+            }
+            _ => {}
+        }
+
+        start.get_or_insert(index);
+    }
+
+    if quote.is_some() || depth > 0 {
+        return Err(unterminated_options(source));
+    }
+
+    options.extend(start.map(|start| &source[start..]));
+
+    Ok(options)
+}
+
+fn unterminated_options(source: &str) -> Error {
+    Error::ParsePassPipeline(format!("unterminated pass options: {source}"))
+}
+
+fn unquote(value: &str) -> &str {
+    [('"', '"'), ('\'', '\''), ('{', '}')]
+        .into_iter()
+        .find_map(|(start, end)| value.strip_prefix(start)?.strip_suffix(end))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Default, Eq, PartialEq, crate::PassOptions)]
+    struct Options {
+        threshold: usize,
+        max_iterations: u32,
+        name: String,
+        verbose: bool,
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Options::parse("threshold=4 max-iterations=8").unwrap(),
+            Options {
+                threshold: 4,
+                max_iterations: 8,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(Options::parse(" ").unwrap(), Options::default());
+    }
+
+    #[test]
+    fn parse_flag() {
+        assert!(Options::parse("verbose").unwrap().verbose);
+    }
+
+    #[test]
+    fn parse_quoted_value() {
+        assert_eq!(
+            Options::parse(r#"name="foo bar" threshold=1"#)
+                .unwrap()
+                .name,
+            "foo bar"
+        );
+        assert_eq!(Options::parse("name={a b}").unwrap().name, "a b");
+    }
+
+    #[test]
+    fn parse_unknown_option() {
+        assert_eq!(
+            Options::parse("foo=1"),
+            Err(Error::ParsePassPipeline("unknown pass option: foo".into()))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_value() {
+        assert_eq!(
+            Options::parse("threshold=foo"),
+            Err(Error::ParsePassPipeline(
+                "invalid value of pass option threshold: foo".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_unterminated() {
+        assert!(Options::parse("name=\"foo").is_err());
+        assert!(Options::parse("name={foo").is_err());
+        assert!(Options::parse("name=foo}").is_err());
+    }
+
+    #[test]
+    fn parse_without_options() {
+        assert_eq!(<()>::parse(""), Ok(()));
+        assert!(<()>::parse("foo").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+/// An element of a textual pass pipeline.
+#[derive(Debug, Eq, PartialEq)]
+pub(super) enum Element<'a> {
+    Pass {
+        name: &'a str,
+        options: &'a str,
+        source: &'a str,
+    },
+    Nested {
+        name: &'a str,
+        elements: Vec<Element<'a>>,
+        source: &'a str,
+    },
+}
+
+impl<'a> Element<'a> {
+    pub(super) const fn name(&self) -> &'a str {
+        match self {
+            Self::Pass { name, .. } | Self::Nested { name, .. } => name,
+        }
+    }
+
+    pub(super) const fn source(&self) -> &'a str {
+        match self {
+            Self::Pass { source, .. } | Self::Nested { source, .. } => source,
+        }
+    }
+}
+
+/// Parses elements of a textual pass pipeline.
+pub(super) fn parse_elements(source: &str) -> Option<Vec<Element<'_>>> {
+    let mut position = 0;
+    let elements = parse_element_list(source, &mut position)?;
+
+    (position == source.len()).then_some(elements)
+}
+
+fn parse_element_list<'a>(source: &'a str, position: &mut usize) -> Option<Vec<Element<'a>>> {
+    let mut elements = vec![];
+
+    skip_whitespace(source, position);
+
+    if source[*position..].starts_with(')') || *position == source.len() {
+        return Some(elements);
+    }
+
+    loop {
+        elements.push(parse_element(source, position)?);
+        skip_whitespace(source, position);
+
+        if source[*position..].starts_with(',') {
+            *position += 1;
+        } else {
+            return Some(elements);
+        }
+    }
+}
+
+fn parse_element<'a>(source: &'a str, position: &mut usize) -> Option<Element<'a>> {
+    skip_whitespace(source, position);
+
+    let start = *position;
+    let length = source[start..]
+        .find(|character: char| "{}(),".contains(character) || character.is_whitespace())
+        .unwrap_or(source.len() - start);
+    let name = &source[start..start + length];
+
+    if name.is_empty() {
+        return None;
+    }
+
+    *position += length;
+    skip_whitespace(source, position);
+
+    if source[*position..].starts_with('{') {
+        let options_start = *position + 1;
+        *position = find_closing_brace(source, *position)?;
+        let options = &source[options_start..*position];
+        *position += 1;
+
+        Some(Element::Pass {
+            name,
+            options,
+            source: &source[start..*position],
+        })
+    } else if source[*position..].starts_with('(') {
+        *position += 1;
+        let elements = parse_element_list(source, position)?;
+
+        if !source[*position..].starts_with(')') {
+            return None;
+        }
+
+        *position += 1;
+
+        Some(Element::Nested {
+            name,
+            elements,
+            source: &source[start..*position],
+        })
+    } else {
+        Some(Element::Pass {
+            name,
+            options: "",
+            source: name,
+        })
+    }
+}
+
+fn find_closing_brace(source: &str, position: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+
+    for (index, character) in source[position..].char_indices() {
+        match (quote, character) {
+            (Some(delimiter), _) if character == delimiter => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(character),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(position + index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn skip_whitespace(source: &str, position: &mut usize) {
+    *position = source.len() - source[*position..].trim_start().len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_elements("builtin.module(foo{a=1 b={2}}, func.func(bar))"),
+            Some(vec![Element::Nested {
+                name: "builtin.module",
+                elements: vec![
+                    Element::Pass {
+                        name: "foo",
+                        options: "a=1 b={2}",
+                        source: "foo{a=1 b={2}}",
+                    },
+                    Element::Nested {
+                        name: "func.func",
+                        elements: vec![Element::Pass {
+                            name: "bar",
+                            options: "",
+                            source: "bar",
+                        }],
+                        source: "func.func(bar)",
+                    },
+                ],
+                source: "builtin.module(foo{a=1 b={2}}, func.func(bar))",
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_empty_nested_pipeline() {
+        assert_eq!(
+            parse_elements("builtin.module()"),
+            Some(vec![Element::Nested {
+                name: "builtin.module",
+                elements: vec![],
+                source: "builtin.module()",
+            }])
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(parse_elements("builtin.module(foo"), None);
+        assert_eq!(parse_elements("foo{a=1"), None);
+        assert_eq!(parse_elements("foo)"), None);
+        assert_eq!(parse_elements("foo,,bar"), None);
+    }
+}
@@ -0,0 +1,344 @@
+use super::{
+    pipeline::{parse_elements, Element},
+    OperationPassManager, Pass, PassOptions,
+};
+use crate::{
+    utility::{add_native_pass_pipeline, parse_native_pass_pipeline},
+    Error,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+type PassFactory = dyn Fn(&str) -> Result<Pass, Error> + Send + Sync;
+
+struct Registration {
+    description: String,
+    factory: Arc<PassFactory>,
+}
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, Registration>>> = LazyLock::new(Default::default);
+
+/// Registers an external pass so that it can be referenced by its argument in
+/// textual pass pipelines parsed by
+/// [`parse_pass_pipeline`](crate::utility::parse_pass_pipeline).
+///
+/// A factory creates a pass from options parsed from a pipeline. A pass
+/// registered with the same argument before is replaced.
+///
+/// # Examples
+///
+/// ```
+/// use melior::{
+///     ir::{r#type::TypeId, OperationRef},
+///     pass::{create_external, register_external_pass, ExternalPass, PassManager},
+///     utility::parse_pass_pipeline,
+///     Context, PassOptions,
+/// };
+///
+/// #[repr(align(8))]
+/// struct PassId;
+///
+/// static EXAMPLE_PASS: PassId = PassId;
+///
+/// #[derive(Default, PassOptions)]
+/// struct ExampleOptions {
+///     threshold: usize,
+/// }
+///
+/// register_external_pass(
+///     "example",
+///     "An example pass",
+///     |options: ExampleOptions| {
+///         create_external(
+///             move |_: OperationRef, _: ExternalPass| println!("{}", options.threshold),
+///             TypeId::create(&EXAMPLE_PASS),
+///             "example",
+///             "example",
+///             "An example pass",
+///             "",
+///             &[],
+///         )
+///     },
+/// );
+///
+/// let context = Context::new();
+/// let manager = PassManager::new(&context);
+///
+/// parse_pass_pipeline(
+///     manager.as_operation_pass_manager(),
+///     "builtin.module(example{threshold=4})",
+/// )
+/// .unwrap();
+/// ```
+pub fn register_external_pass<T: PassOptions>(
+    argument: &str,
+    description: &str,
+    factory: impl Fn(T) -> Pass + Send + Sync + 'static,
+) {
+    REGISTRY.write().expect("valid pass registry").insert(
+        argument.into(),
+        Registration {
+            description: description.into(),
+            factory: Arc::new(move |options| Ok(factory(T::parse(options)?))),
+        },
+    );
+}
+
+/// Returns arguments and descriptions of registered external passes sorted by
+/// their arguments.
+pub fn registered_external_passes() -> Vec<(String, String)> {
+    let mut passes = REGISTRY
+        .read()
+        .expect("valid pass registry")
+        .iter()
+        .map(|(argument, registration)| (argument.clone(), registration.description.clone()))
+        .collect::<Vec<_>>();
+
+    passes.sort();
+    passes
+}
+
+/// Parses a pass pipeline with registered external passes.
+///
+/// It returns `None` if the pipeline does not reference any external passes
+/// and should be parsed by MLIR as it is. Otherwise, or if a pass manager has
+/// instrumentations, passes are added one by one so that external passes are
+/// created by their factories and all passes are instrumented.
+pub(crate) fn parse_pass_pipeline(
+    manager: OperationPassManager,
+    source: &str,
+) -> Option<Result<(), Error>> {
+    let elements = parse_elements(source)?;
+    // Factories are cloned out of the registry so that they can register other
+    // passes without deadlocks.
+    let factories = REGISTRY
+        .read()
+        .expect("valid pass registry")
+        .iter()
+        .map(|(argument, registration)| (argument.clone(), registration.factory.clone()))
+        .collect::<HashMap<_, _>>();
+
+    if manager.instrumentations().is_none() && !contains_external_pass(&factories, &elements) {
+        return None;
+    }
+
+    Some((|| {
+        let [Element::Nested { name, elements, .. }] = elements.as_slice() else {
+            return Err(Error::ParsePassPipeline(format!(
+                "pass pipeline must be anchored on an operation: {source}"
+            )));
+        };
+
+        // Validate the anchor of the pipeline with MLIR.
+        parse_native_pass_pipeline(manager, &format!("{name}()"))?;
+
+        add_elements(&factories, manager, elements)
+    })())
+}
+
+fn contains_external_pass(
+    factories: &HashMap<String, Arc<PassFactory>>,
+    elements: &[Element],
+) -> bool {
+    elements.iter().any(|element| match element {
+        Element::Pass { name, .. } => factories.contains_key(*name),
+        Element::Nested { elements, .. } => contains_external_pass(factories, elements),
+    })
+}
+
+fn add_elements(
+    factories: &HashMap<String, Arc<PassFactory>>,
+    manager: OperationPassManager,
+    elements: &[Element],
+) -> Result<(), Error> {
+    for element in elements {
+        match element {
+            Element::Pass { name, options, .. } if factories.contains_key(*name) => {
+                manager.add_pass(factories[*name](options)?);
+            }
+            Element::Nested { name, elements, .. }
+                if manager.instrumentations().is_some()
+                    || contains_external_pass(factories, elements) =>
+            {
+                add_elements(factories, manager.nested_under(name), elements)?;
+            }
+            _ => match manager.instrumentations() {
+                Some(instrumentations) => {
+                    instrumentations.add_passes(&manager, element.name(), || {
+                        add_native_pass_pipeline(manager, element.source())
+                    })?
+                }
+                None => add_native_pass_pipeline(manager, element.source())?,
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{operation::OperationLike, r#type::TypeId, Module, OperationRef},
+        pass::{create_external, ExternalPass, PassManager},
+        test::create_test_context,
+        utility::{self, register_all_passes},
+        Context,
+    };
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[repr(align(8))]
+    struct PassId;
+
+    #[derive(Default, crate::PassOptions)]
+    struct TestOptions {
+        threshold: usize,
+    }
+
+    #[test]
+    fn register() {
+        register_external_pass(
+            "test-registered-pass",
+            "A registered pass",
+            |_: ()| unreachable!(),
+        );
+
+        assert!(registered_external_passes()
+            .contains(&("test-registered-pass".into(), "A registered pass".into())));
+    }
+
+    #[test]
+    fn parse_pass_pipeline_with_external_pass() {
+        static TEST_PASS: PassId = PassId;
+        static THRESHOLD: AtomicUsize = AtomicUsize::new(0);
+
+        register_all_passes();
+        register_external_pass(
+            "test-external-pass",
+            "A test pass",
+            |options: TestOptions| {
+                create_external(
+                    move |_: OperationRef, _: ExternalPass| {
+                        THRESHOLD.store(options.threshold, Ordering::SeqCst);
+                    },
+                    TypeId::create(&TEST_PASS),
+                    "test external pass",
+                    "test-external-pass",
+                    "A test pass",
+                    "",
+                    &[],
+                )
+            },
+        );
+
+        let context = create_test_context();
+        let manager = PassManager::new(&context);
+
+        utility::parse_pass_pipeline(
+            manager.as_operation_pass_manager(),
+            "builtin.module(canonicalize, func.func(test-external-pass{threshold=4}, cse))",
+        )
+        .unwrap();
+
+        let mut module = Module::parse(&context, "func.func @foo() { return }").unwrap();
+
+        manager.run(&mut module).unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(THRESHOLD.load(Ordering::SeqCst), 4);
+        assert!(manager
+            .as_operation_pass_manager()
+            .to_string()
+            .starts_with("builtin.module(canonicalize{"));
+    }
+
+    #[test]
+    fn parse_pass_pipeline_with_invalid_options() {
+        static TEST_PASS: PassId = PassId;
+
+        register_external_pass(
+            "test-external-pass-with-options",
+            "A test pass",
+            |_: TestOptions| {
+                create_external(
+                    |_: OperationRef, _: ExternalPass| {},
+                    TypeId::create(&TEST_PASS),
+                    "test external pass",
+                    "test-external-pass-with-options",
+                    "A test pass",
+                    "",
+                    &[],
+                )
+            },
+        );
+
+        let context = Context::new();
+        let manager = PassManager::new(&context);
+
+        assert_eq!(
+            utility::parse_pass_pipeline(
+                manager.as_operation_pass_manager(),
+                "builtin.module(test-external-pass-with-options{foo=1})",
+            ),
+            Err(Error::ParsePassPipeline("unknown pass option: foo".into()))
+        );
+    }
+
+    #[test]
+    fn parse_pass_pipeline_with_registering_factory() {
+        static TEST_PASS: PassId = PassId;
+
+        register_external_pass("test-registering-pass", "A test pass", |_: ()| {
+            register_external_pass(
+                "test-registered-by-factory-pass",
+                "A test pass",
+                |_: ()| unreachable!(),
+            );
+
+            create_external(
+                |_: OperationRef, _: ExternalPass| {},
+                TypeId::create(&TEST_PASS),
+                "test external pass",
+                "test-registering-pass",
+                "A test pass",
+                "",
+                &[],
+            )
+        });
+
+        let context = Context::new();
+        let manager = PassManager::new(&context);
+
+        utility::parse_pass_pipeline(
+            manager.as_operation_pass_manager(),
+            "builtin.module(test-registering-pass)",
+        )
+        .unwrap();
+
+        assert!(registered_external_passes()
+            .iter()
+            .any(|(argument, _)| argument == "test-registered-by-factory-pass"));
+    }
+
+    #[test]
+    fn parse_pass_pipeline_without_anchor() {
+        register_external_pass(
+            "test-unanchored-pass",
+            "A test pass",
+            |_: ()| unreachable!(),
+        );
+
+        let context = Context::new();
+        let manager = PassManager::new(&context);
+
+        assert!(utility::parse_pass_pipeline(
+            manager.as_operation_pass_manager(),
+            "test-unanchored-pass",
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Statistics of passes paired with their names.
+pub(super) type PassStatistics = Vec<(String, Vec<Statistic>)>;
+
+/// A pass statistic.
+///
+/// Clones of a statistic share the same counter so that it accumulates values
+/// from all instances of a pass, which MLIR clones for multi-threaded
+/// execution.
+#[derive(Clone, Debug)]
+pub struct Statistic {
+    name: &'static str,
+    description: &'static str,
+    value: Arc<AtomicU64>,
+}
+
+impl Statistic {
+    /// Creates a statistic.
+    pub fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            value: Default::default(),
+        }
+    }
+
+    /// Returns a name.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns a description.
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Returns a value.
+    pub fn value(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /// Increments a value.
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    /// Adds a value.
+    pub fn add(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+    }
+}
+
+impl Display for Statistic {
+    /// Formats a statistic in the same format as `--mlir-pass-statistics`.
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "(S) {} {} - {}",
+            self.value(),
+            self.name,
+            self.description
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn new() {
+        let statistic = Statistic::new("num-foo", "Number of foo");
+
+        assert_eq!(statistic.name(), "num-foo");
+        assert_eq!(statistic.description(), "Number of foo");
+        assert_eq!(statistic.value(), 0);
+    }
+
+    #[test]
+    fn add() {
+        let statistic = Statistic::new("num-foo", "Number of foo");
+        let clone = statistic.clone();
+
+        statistic.increment();
+        clone.add(2);
+
+        assert_eq!(statistic.value(), 3);
+        assert_eq!(clone.value(), 3);
+    }
+
+    #[test]
+    fn display() {
+        let statistic = Statistic::new("num-foo", "Number of foo");
+        statistic.add(42);
+
+        assert_eq!(statistic.to_string(), "(S) 42 num-foo - Number of foo");
+    }
+}
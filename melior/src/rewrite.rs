@@ -0,0 +1,22 @@
+//! Rewrite patterns and pattern rewrite drivers.
+
+mod conversion;
+mod conversion_target;
+mod greedy;
+mod pattern;
+mod pattern_rewriter;
+mod pdl;
+mod type_converter;
+
+pub use self::{
+    conversion::{
+        apply_full_conversion, apply_partial_conversion, ConversionConfig, ConversionPattern,
+        ConversionPatternRewriter, ConversionPatternSet, FunctionSignatureConversion,
+    },
+    conversion_target::ConversionTarget,
+    greedy::{apply_patterns_and_fold_greedily, GreedyRewriteConfig},
+    pattern::{FrozenRewritePatternSet, RewritePattern, RewritePatternSet},
+    pattern_rewriter::PatternRewriter,
+    pdl::PdlPatternModule,
+    type_converter::TypeConverter,
+};
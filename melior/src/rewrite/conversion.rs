@@ -0,0 +1,984 @@
+use super::{greedy::collect_operations, ConversionTarget, PatternRewriter, TypeConverter};
+use crate::{
+    context::Context,
+    ir::{
+        attribute::TypeAttribute,
+        operation::{OperationLike, OperationMutLike, OperationResult},
+        r#type::FunctionType,
+        BlockLike, Location, Module, OperationRef, RegionLike, RegionRef, Type, Value, ValueLike,
+    },
+    Error,
+};
+use mlir_sys::{MlirOperation, MlirValue};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    ops::{Deref, DerefMut},
+};
+
+/// A configuration of the dialect conversion driver.
+#[derive(Clone, Debug)]
+pub struct ConversionConfig {
+    /// The maximum number of iterations over operations.
+    pub max_iterations: usize,
+}
+
+impl Default for ConversionConfig {
+    fn default() -> Self {
+        Self { max_iterations: 10 }
+    }
+}
+
+/// A conversion pattern.
+///
+/// Unlike [`RewritePattern`](super::RewritePattern), it receives operands
+/// already converted by a type converter. Operands whose types are converted
+/// into multiple or no types are passed unconverted as such conversions of
+/// operands are not supported.
+pub trait ConversionPattern<'c> {
+    /// Returns a name of root operations matched by the pattern.
+    ///
+    /// If it is `None`, the pattern is tried on operations of any names.
+    fn root_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns a benefit of the pattern.
+    ///
+    /// Patterns with larger benefits are tried first.
+    fn benefit(&self) -> u16 {
+        1
+    }
+
+    /// Matches an operation and rewrites it with converted operands.
+    ///
+    /// It returns `true` if the pattern has rewritten the IR. All IR
+    /// mutations must be done through the given rewriter.
+    fn match_and_rewrite<'a>(
+        &self,
+        operation: OperationRef<'c, 'a>,
+        operands: &[Value<'c, 'a>],
+        rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+    ) -> bool;
+}
+
+/// A set of conversion patterns.
+#[derive(Default)]
+pub struct ConversionPatternSet<'c> {
+    patterns: Vec<Box<dyn ConversionPattern<'c> + 'c>>,
+}
+
+impl<'c> ConversionPatternSet<'c> {
+    /// Creates a conversion pattern set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern.
+    pub fn add(&mut self, pattern: impl ConversionPattern<'c> + 'c) -> &mut Self {
+        self.patterns.push(Box::new(pattern));
+        self.patterns
+            .sort_by_key(|pattern| Reverse(pattern.benefit()));
+        self
+    }
+
+    /// Returns the number of patterns.
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns `true` if a pattern set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    fn apply<'a>(
+        &self,
+        operation: OperationRef<'c, 'a>,
+        rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+    ) -> bool {
+        let name = operation.name();
+        let name = name.as_string_ref().as_str().ok();
+        let mut patterns = self
+            .patterns
+            .iter()
+            .filter(|pattern| pattern.root_name().is_none() || pattern.root_name() == name)
+            .peekable();
+
+        if patterns.peek().is_none() {
+            return false;
+        }
+
+        let operands = rewriter.remap_operands(operation);
+
+        patterns.any(|pattern| pattern.match_and_rewrite(operation, &operands, rewriter))
+    }
+}
+
+/// A conversion pattern rewriter.
+///
+/// It dereferences to [`PatternRewriter`] and additionally materializes
+/// values whose types are changed by replacements.
+pub struct ConversionPatternRewriter<'c, 'a> {
+    rewriter: PatternRewriter<'c, 'a>,
+    type_converter: &'a TypeConverter<'c>,
+    materializations: HashSet<MlirOperation>,
+    converted_values: HashMap<MlirOperation, MlirValue>,
+}
+
+impl<'c, 'a> ConversionPatternRewriter<'c, 'a> {
+    fn new(context: &'c Context, type_converter: &'a TypeConverter<'c>) -> Self {
+        Self {
+            rewriter: PatternRewriter::new(context),
+            type_converter,
+            materializations: Default::default(),
+            converted_values: Default::default(),
+        }
+    }
+
+    /// Returns a type converter.
+    pub fn type_converter(&self) -> &'a TypeConverter<'c> {
+        self.type_converter
+    }
+
+    /// Replaces results of an operation with converted values and erases the
+    /// operation.
+    ///
+    /// If the type of a value differs from the one of a corresponding result,
+    /// a source materialization is inserted for the remaining uses of the
+    /// result. The number of values must match the number of results.
+    pub fn replace_op(&mut self, operation: OperationRef<'c, 'a>, values: &[Value<'c, 'a>]) {
+        let mut replacements = Vec::with_capacity(values.len());
+
+        self.rewriter.set_insertion_point_before(operation);
+
+        for (result, &value) in operation.results().zip(values) {
+            if result.r#type() == value.r#type() {
+                replacements.push(value);
+                continue;
+            }
+
+            let materialization = self.rewriter.insert(self.type_converter.materialize_source(
+                result.r#type(),
+                &[value],
+                operation.location(),
+            ));
+
+            self.materializations.insert(materialization.to_raw());
+            self.converted_values
+                .insert(materialization.to_raw(), value.to_raw());
+            replacements.push(
+                materialization
+                    .result(0)
+                    .expect("materialized value")
+                    .into(),
+            );
+        }
+
+        self.rewriter.replace_op(operation, &replacements);
+    }
+
+    /// Converts types of arguments of blocks in a region.
+    ///
+    /// An argument converted into a single type is updated in place. An
+    /// argument converted into multiple types is replaced with new arguments of
+    /// those types, and one converted into no type is removed. Source
+    /// materializations are inserted at the starts of the blocks for the
+    /// remaining uses of the original arguments.
+    ///
+    /// It returns an error without changing the region if the type of any
+    /// argument cannot be converted.
+    pub fn convert_region_types(&mut self, region: RegionRef<'c, 'a>) -> Result<(), Error> {
+        self.check_region_types(region)?;
+
+        for block in region.blocks() {
+            let location = block
+                .parent_operation()
+                .map(|operation| operation.location())
+                .unwrap_or_else(|| Location::unknown(unsafe { self.context().to_ref() }));
+            let mut index = 0;
+
+            while index < block.argument_count() {
+                let argument = block.argument(index)?;
+                let r#type = argument.r#type();
+                let types = self
+                    .type_converter
+                    .convert_type(r#type)
+                    .ok_or_else(|| Error::ConvertType(r#type.to_string()))?;
+
+                if types == [r#type] {
+                    index += 1;
+                    continue;
+                }
+
+                self.rewriter.set_insertion_point_to_start(block);
+
+                if let [converted] = types[..] {
+                    argument.set_type(converted);
+
+                    let materialization =
+                        self.rewriter.insert(self.type_converter.materialize_source(
+                            r#type,
+                            &[argument.into()],
+                            location,
+                        ));
+
+                    argument.replace_all_uses_except(
+                        materialization.result(0).expect("materialized value"),
+                        materialization,
+                    );
+                    self.materializations.insert(materialization.to_raw());
+                    self.converted_values
+                        .insert(materialization.to_raw(), argument.to_raw());
+                } else {
+                    let arguments = types
+                        .iter()
+                        .enumerate()
+                        .map(|(offset, &r#type)| {
+                            block.insert_argument(index + offset + 1, r#type, location)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    let materialization = self.rewriter.insert(
+                        self.type_converter
+                            .materialize_source(r#type, &arguments, location),
+                    );
+
+                    argument.replace_all_uses_with(
+                        materialization.result(0).expect("materialized value"),
+                    );
+                    self.materializations.insert(materialization.to_raw());
+                    block.erase_argument(index)?;
+                }
+
+                index += types.len();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_region_types(&self, region: RegionRef<'c, 'a>) -> Result<(), Error> {
+        for block in region.blocks() {
+            for index in 0..block.argument_count() {
+                let r#type = block.argument(index)?.r#type();
+
+                if self.type_converter.convert_type(r#type).is_none() {
+                    return Err(Error::ConvertType(r#type.to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remap_operands(&mut self, operation: OperationRef<'c, 'a>) -> Vec<Value<'c, 'a>> {
+        let mut operands = vec![];
+
+        for operand in operation.operands() {
+            if let Some(&value) = OperationResult::try_from(operand)
+                .ok()
+                .and_then(|result| self.converted_values.get(&result.owner().to_raw()))
+            {
+                operands.push(unsafe { Value::from_raw(value) });
+                continue;
+            }
+
+            let r#type = operand.r#type();
+
+            match self.type_converter.convert_type(r#type).as_deref() {
+                Some(&[converted]) if converted == r#type => operands.push(operand),
+                Some(&[converted]) => {
+                    self.rewriter.set_insertion_point_before(operation);
+
+                    let materialization =
+                        self.rewriter.insert(self.type_converter.materialize_target(
+                            converted,
+                            &[operand],
+                            operation.location(),
+                        ));
+
+                    self.materializations.insert(materialization.to_raw());
+                    operands.push(
+                        materialization
+                            .result(0)
+                            .expect("materialized value")
+                            .into(),
+                    );
+                }
+                // Operands converted into multiple or no types are not supported.
+                Some(_) | None => operands.push(operand),
+            }
+        }
+
+        operands
+    }
+
+    fn is_materialization(&self, operation: MlirOperation) -> bool {
+        self.materializations.contains(&operation)
+    }
+
+    fn clear_erased(&mut self) {
+        self.materializations
+            .retain(|&operation| !self.rewriter.is_erased(operation));
+        self.converted_values
+            .retain(|operation, _| !self.rewriter.is_erased(*operation));
+        self.rewriter.clear_erased();
+    }
+
+    fn erase_unused_materializations(&mut self) {
+        loop {
+            let unused = self
+                .materializations
+                .iter()
+                .copied()
+                .filter(|&operation| {
+                    unsafe { OperationRef::from_raw(operation) }
+                        .results()
+                        .all(|result| result.use_empty())
+                })
+                .collect::<Vec<_>>();
+
+            if unused.is_empty() {
+                break;
+            }
+
+            for operation in unused {
+                self.materializations.remove(&operation);
+                self.converted_values.remove(&operation);
+                self.rewriter
+                    .erase_op(unsafe { OperationRef::from_raw(operation) });
+            }
+        }
+
+        self.rewriter.clear_erased();
+    }
+}
+
+impl<'c, 'a> Deref for ConversionPatternRewriter<'c, 'a> {
+    type Target = PatternRewriter<'c, 'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rewriter
+    }
+}
+
+impl DerefMut for ConversionPatternRewriter<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rewriter
+    }
+}
+
+/// A conversion pattern which converts signatures of `func.func` operations
+/// and types of arguments of blocks in their bodies.
+///
+/// Operations returning values from the functions need to be converted by
+/// other patterns.
+pub struct FunctionSignatureConversion;
+
+impl<'c> ConversionPattern<'c> for FunctionSignatureConversion {
+    fn root_name(&self) -> Option<&str> {
+        Some("func.func")
+    }
+
+    fn match_and_rewrite<'a>(
+        &self,
+        operation: OperationRef<'c, 'a>,
+        _operands: &[Value<'c, 'a>],
+        rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+    ) -> bool {
+        let Some(r#type) = operation
+            .attribute("function_type")
+            .ok()
+            .and_then(|attribute| TypeAttribute::try_from(attribute).ok())
+            .and_then(|attribute| FunctionType::try_from(attribute.value()).ok())
+        else {
+            return false;
+        };
+        let Some(converted) = rewriter.type_converter().convert_function_type(r#type) else {
+            return false;
+        };
+
+        if Type::from(converted) == Type::from(r#type)
+            || operation
+                .regions()
+                .any(|region| rewriter.check_region_types(region).is_err())
+        {
+            return false;
+        }
+
+        rewriter.modify_op_in_place(operation, |mut operation| {
+            operation.set_attribute("function_type", TypeAttribute::new(converted.into()).into());
+        });
+
+        for region in operation.regions() {
+            rewriter
+                .convert_region_types(region)
+                .expect("convertible region types");
+        }
+
+        true
+    }
+}
+
+/// Applies a partial conversion to a module.
+///
+/// It legalizes as many operations as possible. It returns an error only if
+/// any operation explicitly marked illegal remains after conversion.
+///
+/// Unlike the dialect conversion in MLIR, changes made by patterns are not
+/// rolled back on failure. The module might be partially converted if an
+/// error is returned.
+pub fn apply_partial_conversion<'c>(
+    module: &mut Module<'c>,
+    target: &ConversionTarget<'c>,
+    patterns: &ConversionPatternSet<'c>,
+    type_converter: &TypeConverter<'c>,
+    config: &ConversionConfig,
+) -> Result<(), Error> {
+    apply_conversion(module, target, patterns, type_converter, config, false)
+}
+
+/// Applies a full conversion to a module.
+///
+/// It returns an error if any operation that is not legal remains after
+/// conversion.
+///
+/// Unlike the dialect conversion in MLIR, changes made by patterns are not
+/// rolled back on failure. The module might be partially converted if an
+/// error is returned.
+pub fn apply_full_conversion<'c>(
+    module: &mut Module<'c>,
+    target: &ConversionTarget<'c>,
+    patterns: &ConversionPatternSet<'c>,
+    type_converter: &TypeConverter<'c>,
+    config: &ConversionConfig,
+) -> Result<(), Error> {
+    apply_conversion(module, target, patterns, type_converter, config, true)
+}
+
+fn apply_conversion<'c>(
+    module: &mut Module<'c>,
+    target: &ConversionTarget<'c>,
+    patterns: &ConversionPatternSet<'c>,
+    type_converter: &TypeConverter<'c>,
+    config: &ConversionConfig,
+    full: bool,
+) -> Result<(), Error> {
+    let context = module.context();
+    let mut rewriter = ConversionPatternRewriter::new(unsafe { context.to_ref() }, type_converter);
+
+    for _ in 0..config.max_iterations {
+        let mut changed = false;
+
+        for operation in collect_operations(module, true) {
+            if rewriter.is_erased(operation) || rewriter.is_materialization(operation) {
+                continue;
+            }
+
+            let operation = unsafe { OperationRef::from_raw(operation) };
+
+            if target.is_legal(operation) != Some(true) {
+                changed |= patterns.apply(operation, &mut rewriter);
+            }
+        }
+
+        rewriter.clear_erased();
+
+        if !changed {
+            break;
+        }
+    }
+
+    rewriter.erase_unused_materializations();
+
+    for operation in collect_operations(module, true) {
+        if rewriter.is_materialization(operation) {
+            continue;
+        }
+
+        let operation = unsafe { OperationRef::from_raw(operation) };
+
+        match target.is_legal(operation) {
+            Some(true) => {}
+            None if !full => {}
+            _ => {
+                return Err(Error::LegalizeOperation(
+                    operation.name().as_string_ref().as_str()?.into(),
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::{arith, func},
+        ir::{attribute::IntegerAttribute, r#type::IntegerType, TypeLike},
+        test::create_test_context,
+    };
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    struct ReplaceWithAddition;
+
+    impl<'c> ConversionPattern<'c> for ReplaceWithAddition {
+        fn root_name(&self) -> Option<&str> {
+            Some("arith.muli")
+        }
+
+        fn match_and_rewrite<'a>(
+            &self,
+            operation: OperationRef<'c, 'a>,
+            operands: &[Value<'c, 'a>],
+            rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+        ) -> bool {
+            rewriter.set_insertion_point_before(operation);
+
+            let addition =
+                rewriter.insert(arith::addi(operands[0], operands[1], operation.location()));
+
+            rewriter.replace_op(operation, &[addition.result(0).unwrap().into()]);
+
+            true
+        }
+    }
+
+    struct ConvertIndexConstant;
+
+    impl<'c> ConversionPattern<'c> for ConvertIndexConstant {
+        fn root_name(&self) -> Option<&str> {
+            Some("arith.constant")
+        }
+
+        fn match_and_rewrite<'a>(
+            &self,
+            operation: OperationRef<'c, 'a>,
+            _operands: &[Value<'c, 'a>],
+            rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+        ) -> bool {
+            let r#type = operation.result(0).unwrap().r#type();
+            let Some(&[converted]) = rewriter.type_converter().convert_type(r#type).as_deref()
+            else {
+                return false;
+            };
+
+            rewriter.set_insertion_point_before(operation);
+
+            let value = IntegerAttribute::try_from(operation.attribute("value").unwrap())
+                .unwrap()
+                .value();
+            let context = rewriter.context();
+            let constant = rewriter.insert(arith::constant(
+                unsafe { context.to_ref() },
+                IntegerAttribute::new(converted, value).into(),
+                operation.location(),
+            ));
+
+            rewriter.replace_op(operation, &[constant.result(0).unwrap().into()]);
+
+            true
+        }
+    }
+
+    struct ConvertReturn;
+
+    impl<'c> ConversionPattern<'c> for ConvertReturn {
+        fn root_name(&self) -> Option<&str> {
+            Some("func.return")
+        }
+
+        fn match_and_rewrite<'a>(
+            &self,
+            operation: OperationRef<'c, 'a>,
+            operands: &[Value<'c, 'a>],
+            rewriter: &mut ConversionPatternRewriter<'c, 'a>,
+        ) -> bool {
+            if operation.operands().eq(operands.iter().copied()) {
+                return false;
+            }
+
+            rewriter.set_insertion_point_before(operation);
+            rewriter.insert(func::r#return(operands, operation.location()));
+            rewriter.replace_op(operation, &[]);
+
+            true
+        }
+    }
+
+    fn create_module(context: &Context) -> Module<'_> {
+        Module::parse(
+            context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.muli %arg0, %arg1 : i32
+                    return %0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn add() {
+        let mut patterns = ConversionPatternSet::new();
+
+        assert!(patterns.is_empty());
+
+        patterns.add(ReplaceWithAddition);
+
+        assert_eq!(patterns.len(), 1);
+    }
+
+    #[test]
+    fn apply_partial() {
+        let context = create_test_context();
+        let mut module = create_module(&context);
+        let mut target = ConversionTarget::new();
+        target.add_illegal_operation("arith.muli");
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(ReplaceWithAddition);
+
+        apply_partial_conversion(
+            &mut module,
+            &target,
+            &patterns,
+            &TypeConverter::new(),
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i32, %arg1: i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 : i32
+                    return %0 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_partial_with_illegal_operation() {
+        let context = create_test_context();
+        let mut module = create_module(&context);
+        let mut target = ConversionTarget::new();
+        target.add_illegal_operation("arith.muli");
+
+        assert_eq!(
+            apply_partial_conversion(
+                &mut module,
+                &target,
+                &ConversionPatternSet::new(),
+                &TypeConverter::new(),
+                &ConversionConfig::default()
+            ),
+            Err(Error::LegalizeOperation("arith.muli".into()))
+        );
+    }
+
+    #[test]
+    fn apply_full() {
+        let context = create_test_context();
+        let mut module = create_module(&context);
+        let mut target = ConversionTarget::new();
+        target
+            .add_legal_dialect("func")
+            .add_legal_dialect("arith")
+            .add_illegal_operation("arith.muli");
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(ReplaceWithAddition);
+
+        apply_full_conversion(
+            &mut module,
+            &target,
+            &patterns,
+            &TypeConverter::new(),
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+    }
+
+    #[test]
+    fn apply_full_with_unknown_operation() {
+        let context = create_test_context();
+        let mut module = create_module(&context);
+        let mut target = ConversionTarget::new();
+        target
+            .add_legal_dialect("arith")
+            .add_illegal_operation("arith.muli");
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(ReplaceWithAddition);
+
+        assert_eq!(
+            apply_full_conversion(
+                &mut module,
+                &target,
+                &patterns,
+                &TypeConverter::new(),
+                &ConversionConfig::default()
+            ),
+            Err(Error::LegalizeOperation("func.func".into()))
+        );
+    }
+
+    #[test]
+    fn apply_with_type_conversion() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo() -> index {
+                    %0 = arith.constant 42 : index
+                    return %0 : index
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let index = Type::index(&context);
+        let i64 = IntegerType::new(&context, 64).into();
+
+        let mut type_converter = TypeConverter::new();
+        type_converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i64]))
+            .add_source_materialization(|r#type, inputs, location| {
+                Some(arith::index_cast(inputs[0], r#type, location))
+            });
+
+        let mut target = ConversionTarget::new();
+        target.add_dynamically_legal_operation("arith.constant", |operation| {
+            !operation.result(0).unwrap().r#type().is_index()
+        });
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(ConvertIndexConstant);
+
+        apply_partial_conversion(
+            &mut module,
+            &target,
+            &patterns,
+            &type_converter,
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo() -> index {
+                    %c42_i64 = arith.constant 42 : i64
+                    %0 = arith.index_cast %c42_i64 : i64 to index
+                    return %0 : index
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_with_signature_conversion() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : index) -> index {
+                    return %arg0 : index
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let index = Type::index(&context);
+        let i64 = IntegerType::new(&context, 64).into();
+
+        let mut type_converter = TypeConverter::new();
+        type_converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i64]));
+
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(FunctionSignatureConversion).add(ConvertReturn);
+
+        apply_partial_conversion(
+            &mut module,
+            &ConversionTarget::new(),
+            &patterns,
+            &type_converter,
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i64) -> i64 {
+                    return %arg0 : i64
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_with_one_to_many_signature_conversion() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : index) {
+                    return
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let index = Type::index(&context);
+        let i32 = IntegerType::new(&context, 32).into();
+
+        let mut type_converter = TypeConverter::new();
+        type_converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i32, i32]));
+
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(FunctionSignatureConversion);
+
+        apply_partial_conversion(
+            &mut module,
+            &ConversionTarget::new(),
+            &patterns,
+            &type_converter,
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i32, %arg1: i32) {
+                    return
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_with_unconvertible_block_argument() {
+        let context = create_test_context();
+        let source = indoc!(
+            "
+            module {
+              func.func @foo(%arg0: index) {
+                %cst = arith.constant 1.000000e+00 : f32
+                cf.br ^bb1(%cst : f32)
+              ^bb1(%0: f32):  // pred: ^bb0
+                return
+              }
+            }
+            "
+        );
+        let mut module = Module::parse(&context, source).unwrap();
+        let index = Type::index(&context);
+        let i32 = IntegerType::new(&context, 32).into();
+
+        let mut type_converter = TypeConverter::new();
+        type_converter.add_conversion(move |r#type| (r#type == index).then(|| vec![i32]));
+
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(FunctionSignatureConversion);
+
+        apply_partial_conversion(
+            &mut module,
+            &ConversionTarget::new(),
+            &patterns,
+            &type_converter,
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(module.as_operation().to_string(), source);
+    }
+
+    #[test]
+    fn apply_with_one_to_many_operand_conversion() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : index, %arg1 : index) -> index {
+                    %0 = arith.muli %arg0, %arg1 : index
+                    return %0 : index
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let index = Type::index(&context);
+        let i32 = IntegerType::new(&context, 32).into();
+
+        let mut type_converter = TypeConverter::new();
+        type_converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i32, i32]));
+
+        let mut patterns = ConversionPatternSet::new();
+        patterns.add(ReplaceWithAddition);
+
+        apply_partial_conversion(
+            &mut module,
+            &ConversionTarget::new(),
+            &patterns,
+            &type_converter,
+            &ConversionConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: index, %arg1: index) -> index {
+                    %0 = arith.addi %arg0, %arg1 : index
+                    return %0 : index
+                  }
+                }
+                "
+            )
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::ir::{operation::OperationLike, OperationRef};
+use std::collections::HashMap;
+
+type LegalityCallback<'c> = dyn Fn(OperationRef<'c, '_>) -> bool + 'c;
+
+enum Legality<'c> {
+    Legal,
+    Illegal,
+    Dynamic(Box<LegalityCallback<'c>>),
+}
+
+impl<'c> Legality<'c> {
+    fn is_legal(&self, operation: OperationRef<'c, '_>) -> bool {
+        match self {
+            Self::Legal => true,
+            Self::Illegal => false,
+            Self::Dynamic(callback) => callback(operation),
+        }
+    }
+}
+
+/// A conversion target.
+///
+/// It describes which operations are legal after dialect conversion.
+/// Legality of operations takes precedence over the one of their dialects.
+/// Operations whose legality is not specified are unknown.
+#[derive(Default)]
+pub struct ConversionTarget<'c> {
+    dialects: HashMap<String, Legality<'c>>,
+    operations: HashMap<String, Legality<'c>>,
+    unknown: Option<Box<LegalityCallback<'c>>>,
+}
+
+impl<'c> ConversionTarget<'c> {
+    /// Creates a conversion target.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a dialect legal.
+    pub fn add_legal_dialect(&mut self, name: &str) -> &mut Self {
+        self.dialects.insert(name.into(), Legality::Legal);
+        self
+    }
+
+    /// Marks a dialect illegal.
+    pub fn add_illegal_dialect(&mut self, name: &str) -> &mut Self {
+        self.dialects.insert(name.into(), Legality::Illegal);
+        self
+    }
+
+    /// Marks a dialect dynamically legal with a callback which decides
+    /// legality of its operations.
+    pub fn add_dynamically_legal_dialect(
+        &mut self,
+        name: &str,
+        callback: impl Fn(OperationRef<'c, '_>) -> bool + 'c,
+    ) -> &mut Self {
+        self.dialects
+            .insert(name.into(), Legality::Dynamic(Box::new(callback)));
+        self
+    }
+
+    /// Marks an operation legal.
+    pub fn add_legal_operation(&mut self, name: &str) -> &mut Self {
+        self.operations.insert(name.into(), Legality::Legal);
+        self
+    }
+
+    /// Marks an operation illegal.
+    pub fn add_illegal_operation(&mut self, name: &str) -> &mut Self {
+        self.operations.insert(name.into(), Legality::Illegal);
+        self
+    }
+
+    /// Marks an operation dynamically legal with a callback which decides its
+    /// legality.
+    pub fn add_dynamically_legal_operation(
+        &mut self,
+        name: &str,
+        callback: impl Fn(OperationRef<'c, '_>) -> bool + 'c,
+    ) -> &mut Self {
+        self.operations
+            .insert(name.into(), Legality::Dynamic(Box::new(callback)));
+        self
+    }
+
+    /// Marks operations of unknown legality dynamically legal with a callback.
+    pub fn mark_unknown_operation_dynamically_legal(
+        &mut self,
+        callback: impl Fn(OperationRef<'c, '_>) -> bool + 'c,
+    ) -> &mut Self {
+        self.unknown = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns `Some(true)` if an operation is legal, `Some(false)` if it is
+    /// illegal, or `None` if its legality is unknown.
+    pub fn is_legal(&self, operation: OperationRef<'c, '_>) -> Option<bool> {
+        let name = operation.name();
+        let name = name.as_string_ref().as_str().ok()?;
+
+        self.operations
+            .get(name)
+            .or_else(|| {
+                name.split_once('.')
+                    .and_then(|(dialect, _)| self.dialects.get(dialect))
+            })
+            .map(|legality| legality.is_legal(operation))
+            .or_else(|| self.unknown.as_ref().map(|callback| callback(operation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::arith,
+        ir::{
+            attribute::IntegerAttribute, r#type::IntegerType, Block, BlockLike, Location, Type,
+            TypeLike, ValueLike,
+        },
+        test::create_test_context,
+    };
+
+    #[test]
+    fn is_legal() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let block = Block::new(&[(Type::index(&context), location)]);
+        let argument = block.argument(0).unwrap().into();
+
+        let constant = block.append_operation(arith::constant(
+            &context,
+            IntegerAttribute::new(Type::index(&context), 42).into(),
+            location,
+        ));
+        let addition = block.append_operation(arith::addi(argument, argument, location));
+        let cast = block.append_operation(arith::index_cast(
+            argument,
+            IntegerType::new(&context, 64).into(),
+            location,
+        ));
+
+        let mut target = ConversionTarget::new();
+
+        assert_eq!(target.is_legal(constant), None);
+
+        target
+            .add_illegal_dialect("arith")
+            .add_legal_operation("arith.constant")
+            .add_dynamically_legal_operation("arith.index_cast", |operation| {
+                operation.result(0).unwrap().r#type().is_index()
+            });
+
+        assert_eq!(target.is_legal(constant), Some(true));
+        assert_eq!(target.is_legal(addition), Some(false));
+        assert_eq!(target.is_legal(cast), Some(false));
+    }
+
+    #[test]
+    fn is_legal_unknown() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let block = Block::new(&[]);
+
+        let constant = block.append_operation(arith::constant(
+            &context,
+            IntegerAttribute::new(Type::index(&context), 42).into(),
+            location,
+        ));
+
+        let mut target = ConversionTarget::new();
+        target.mark_unknown_operation_dynamically_legal(|_| true);
+
+        assert_eq!(target.is_legal(constant), Some(true));
+
+        target.add_dynamically_legal_dialect("arith", |_| false);
+
+        assert_eq!(target.is_legal(constant), Some(false));
+    }
+}
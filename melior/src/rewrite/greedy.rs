@@ -0,0 +1,384 @@
+use super::{FrozenRewritePatternSet, PatternRewriter};
+use crate::{
+    ir::{
+        operation::{OperationLike, WalkOrder, WalkResult},
+        Module, OperationRef,
+    },
+    logical_result::LogicalResult,
+    Error,
+};
+use mlir_sys::{
+    mlirApplyPatternsAndFoldGreedily, mlirWalkAndApplyPatterns, MlirGreedyRewriteDriverConfig,
+    MlirOperation,
+};
+use std::ptr::null_mut;
+
+/// A configuration of the greedy pattern rewrite driver.
+#[derive(Clone, Debug)]
+pub struct GreedyRewriteConfig {
+    /// The maximum number of iterations over operations.
+    pub max_iterations: usize,
+    /// Whether operations are visited top-down. If it is `false`, they are
+    /// visited bottom-up.
+    pub use_top_down_traversal: bool,
+    /// Whether to fold operations, erase trivially dead operations, and
+    /// simplify regions after each iteration.
+    ///
+    /// The C API only exposes those steps as a whole, so they are enabled or
+    /// disabled together. If they are disabled, PDL patterns are applied in a
+    /// single walk over operations per iteration instead.
+    pub enable_region_simplification: bool,
+}
+
+impl Default for GreedyRewriteConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 10,
+            use_top_down_traversal: true,
+            enable_region_simplification: true,
+        }
+    }
+}
+
+/// Applies rewrite patterns to operations in a module greedily until no
+/// pattern matches any more.
+///
+/// It returns an error if the patterns do not converge within the maximum
+/// number of iterations.
+///
+/// Each iteration applies Rust patterns and then folds operations or applies
+/// native patterns. The driver stops once an iteration's Rust patterns change
+/// nothing. Changes by folding or native patterns cannot be detected through
+/// the C API, so Rust patterns are not re-applied to operations produced by
+/// folding or native patterns in the last iteration.
+pub fn apply_patterns_and_fold_greedily<'c>(
+    module: &mut Module<'c>,
+    patterns: &FrozenRewritePatternSet<'c>,
+    config: &GreedyRewriteConfig,
+) -> Result<(), Error> {
+    let context = module.context();
+    let mut rewriter = PatternRewriter::new(unsafe { context.to_ref() });
+
+    for _ in 0..config.max_iterations {
+        let mut changed = false;
+
+        for operation in collect_operations(module, config.use_top_down_traversal) {
+            if rewriter.is_erased(operation) {
+                continue;
+            }
+
+            changed |= patterns.apply(unsafe { OperationRef::from_raw(operation) }, &mut rewriter);
+        }
+
+        rewriter.clear_erased();
+
+        // Native patterns are applied only by MLIR's own drivers.
+        if config.enable_region_simplification {
+            fold(module, patterns)?;
+        } else if patterns.has_native_patterns() {
+            unsafe {
+                mlirWalkAndApplyPatterns(module.as_operation().to_raw(), patterns.to_native_raw())
+            }
+        }
+
+        // Folding or native patterns in this iteration may create new matches
+        // for Rust patterns, but they are not detectable.
+        if !changed {
+            return Ok(());
+        }
+    }
+
+    Err(Error::ApplyPatterns)
+}
+
+pub(super) fn collect_operations(module: &Module, top_down: bool) -> Vec<MlirOperation> {
+    let root = module.as_operation().to_raw();
+    let mut operations = vec![];
+
+    module.as_operation().walk(
+        if top_down {
+            WalkOrder::PreOrder
+        } else {
+            WalkOrder::PostOrder
+        },
+        |operation| {
+            if operation.to_raw() != root {
+                operations.push(operation.to_raw());
+            }
+
+            WalkResult::Advance
+        },
+    );
+
+    operations
+}
+
+fn fold(module: &mut Module, patterns: &FrozenRewritePatternSet) -> Result<(), Error> {
+    // The configuration is ignored by the C API.
+    let result = LogicalResult::from_raw(unsafe {
+        mlirApplyPatternsAndFoldGreedily(
+            module.to_raw(),
+            patterns.to_native_raw(),
+            MlirGreedyRewriteDriverConfig { ptr: null_mut() },
+        )
+    });
+
+    if result.is_success() {
+        Ok(())
+    } else {
+        Err(Error::ApplyPatterns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::arith,
+        rewrite::{RewritePattern, RewritePatternSet},
+        test::create_test_context,
+    };
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    struct ReplaceWithAddition {
+        root_name: &'static str,
+    }
+
+    impl<'c> RewritePattern<'c> for ReplaceWithAddition {
+        fn root_name(&self) -> Option<&str> {
+            Some(self.root_name)
+        }
+
+        fn match_and_rewrite<'a>(
+            &self,
+            operation: OperationRef<'c, 'a>,
+            rewriter: &mut PatternRewriter<'c, 'a>,
+        ) -> bool {
+            rewriter.set_insertion_point_before(operation);
+
+            let addition = rewriter.insert(arith::addi(
+                operation.operand(0).unwrap(),
+                operation.operand(1).unwrap(),
+                operation.location(),
+            ));
+
+            rewriter.replace_op_with_operation(operation, addition);
+
+            true
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.muli %arg0, %arg1 : i32
+                    return %0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut patterns = RewritePatternSet::new(&context);
+        patterns.add(ReplaceWithAddition {
+            root_name: "arith.muli",
+        });
+
+        apply_patterns_and_fold_greedily(
+            &mut module,
+            &patterns.freeze(),
+            &GreedyRewriteConfig::default(),
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i32, %arg1: i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 : i32
+                    return %0 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_bottom_up() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.muli %arg0, %arg1 : i32
+                    %1 = arith.muli %0, %arg1 : i32
+                    return %1 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut patterns = RewritePatternSet::new(&context);
+        patterns.add(ReplaceWithAddition {
+            root_name: "arith.muli",
+        });
+
+        apply_patterns_and_fold_greedily(
+            &mut module,
+            &patterns.freeze(),
+            &GreedyRewriteConfig {
+                use_top_down_traversal: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo(%arg0: i32, %arg1: i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 : i32
+                    %1 = arith.addi %0, %arg1 : i32
+                    return %1 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_and_fold() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo() -> i32 {
+                    %0 = arith.constant 1 : i32
+                    %1 = arith.constant 2 : i32
+                    %2 = arith.muli %0, %1 : i32
+                    return %2 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut patterns = RewritePatternSet::new(&context);
+        patterns.add(ReplaceWithAddition {
+            root_name: "arith.muli",
+        });
+
+        apply_patterns_and_fold_greedily(
+            &mut module,
+            &patterns.freeze(),
+            &GreedyRewriteConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo() -> i32 {
+                    %c3_i32 = arith.constant 3 : i32
+                    return %c3_i32 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn apply_without_region_simplification() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo() -> i32 {
+                    %0 = arith.constant 1 : i32
+                    %1 = arith.constant 2 : i32
+                    %2 = arith.muli %0, %1 : i32
+                    return %2 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut patterns = RewritePatternSet::new(&context);
+        patterns.add(ReplaceWithAddition {
+            root_name: "arith.muli",
+        });
+
+        apply_patterns_and_fold_greedily(
+            &mut module,
+            &patterns.freeze(),
+            &GreedyRewriteConfig {
+                enable_region_simplification: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.as_operation().to_string(),
+            indoc!(
+                "
+                module {
+                  func.func @foo() -> i32 {
+                    %c1_i32 = arith.constant 1 : i32
+                    %c2_i32 = arith.constant 2 : i32
+                    %0 = arith.addi %c1_i32, %c2_i32 : i32
+                    return %0 : i32
+                  }
+                }
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn fail_to_converge() {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.addi %arg0, %arg1 : i32
+                    return %0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+        let mut patterns = RewritePatternSet::new(&context);
+        patterns.add(ReplaceWithAddition {
+            root_name: "arith.addi",
+        });
+
+        assert_eq!(
+            apply_patterns_and_fold_greedily(
+                &mut module,
+                &patterns.freeze(),
+                &GreedyRewriteConfig::default(),
+            ),
+            Err(Error::ApplyPatterns)
+        );
+    }
+}
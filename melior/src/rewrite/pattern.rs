@@ -0,0 +1,222 @@
+use super::{PatternRewriter, PdlPatternModule};
+use crate::{
+    context::Context,
+    ir::{
+        operation::OperationLike, BlockLike, Location, Module, Operation, OperationRef, SymbolTable,
+    },
+};
+use mlir_sys::{
+    mlirBlockAppendOwnedOperation, mlirFreezeRewritePattern, mlirFrozenRewritePatternSetDestroy,
+    mlirOperationRemoveFromParent, mlirPDLPatternModuleDestroy, mlirPDLPatternModuleFromModule,
+    mlirRewritePatternSetFromPDLPatternModule, MlirFrozenRewritePatternSet,
+};
+use std::{cmp::Reverse, marker::PhantomData};
+
+/// A rewrite pattern.
+pub trait RewritePattern<'c> {
+    /// Returns a name of root operations matched by the pattern.
+    ///
+    /// If it is `None`, the pattern is tried on operations of any names.
+    fn root_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns a benefit of the pattern.
+    ///
+    /// Patterns with larger benefits are tried first.
+    fn benefit(&self) -> u16 {
+        1
+    }
+
+    /// Matches an operation and rewrites it.
+    ///
+    /// It returns `true` if the pattern has rewritten the IR. All IR
+    /// mutations must be done through the given rewriter.
+    fn match_and_rewrite<'a>(
+        &self,
+        operation: OperationRef<'c, 'a>,
+        rewriter: &mut PatternRewriter<'c, 'a>,
+    ) -> bool;
+}
+
+/// A set of rewrite patterns.
+pub struct RewritePatternSet<'c> {
+    context: &'c Context,
+    patterns: Vec<Box<dyn RewritePattern<'c> + 'c>>,
+    pdl_modules: Vec<PdlPatternModule<'c>>,
+}
+
+impl<'c> RewritePatternSet<'c> {
+    /// Creates a rewrite pattern set.
+    pub fn new(context: &'c Context) -> Self {
+        Self {
+            context,
+            patterns: vec![],
+            pdl_modules: vec![],
+        }
+    }
+
+    /// Adds a pattern.
+    pub fn add(&mut self, pattern: impl RewritePattern<'c> + 'c) -> &mut Self {
+        self.patterns.push(Box::new(pattern));
+        self
+    }
+
+    /// Adds patterns in a PDL pattern module.
+    pub fn add_pdl_module(&mut self, module: PdlPatternModule<'c>) -> &mut Self {
+        self.pdl_modules.push(module);
+        self
+    }
+
+    /// Returns the number of patterns.
+    ///
+    /// Patterns in PDL pattern modules are not counted.
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns `true` if a pattern set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.pdl_modules.is_empty()
+    }
+
+    /// Freezes a pattern set so that it can be applied to IR.
+    pub fn freeze(mut self) -> FrozenRewritePatternSet<'c> {
+        self.patterns
+            .sort_by_key(|pattern| Reverse(pattern.benefit()));
+
+        // The C API does not expose an empty native pattern set directly, so we
+        // build one from a PDL module, which is empty unless PDL pattern modules
+        // are added. It is used to run folding with MLIR's own greedy driver.
+        let module = Module::new(Location::unknown(self.context));
+        let has_native_patterns = !self.pdl_modules.is_empty();
+        let symbol_table = SymbolTable::new(module.as_operation()).expect("module symbol table");
+
+        for pdl_module in self.pdl_modules {
+            let pdl_module = pdl_module.into_module();
+
+            while let Some(operation) = pdl_module.body().first_operation() {
+                let has_symbol = operation
+                    .attribute(SymbolTable::symbol_attribute_name())
+                    .is_ok();
+
+                unsafe {
+                    mlirOperationRemoveFromParent(operation.to_raw());
+
+                    // Patterns from different modules are renamed if their
+                    // symbols conflict.
+                    if has_symbol {
+                        symbol_table.insert(Operation::from_raw(operation.to_raw()));
+                    } else {
+                        mlirBlockAppendOwnedOperation(module.body().to_raw(), operation.to_raw());
+                    }
+                }
+            }
+        }
+
+        drop(symbol_table);
+
+        let native = unsafe {
+            let module = mlirPDLPatternModuleFromModule(module.into_raw());
+            let set = mlirRewritePatternSetFromPDLPatternModule(module);
+            mlirPDLPatternModuleDestroy(module);
+
+            mlirFreezeRewritePattern(set)
+        };
+
+        FrozenRewritePatternSet {
+            native,
+            has_native_patterns,
+            patterns: self.patterns,
+            _context: Default::default(),
+        }
+    }
+}
+
+/// A frozen set of rewrite patterns.
+pub struct FrozenRewritePatternSet<'c> {
+    native: MlirFrozenRewritePatternSet,
+    has_native_patterns: bool,
+    patterns: Vec<Box<dyn RewritePattern<'c> + 'c>>,
+    _context: PhantomData<&'c Context>,
+}
+
+impl<'c> FrozenRewritePatternSet<'c> {
+    /// Applies the first matching pattern to an operation.
+    ///
+    /// It returns `true` if any pattern has rewritten the IR.
+    pub(crate) fn apply<'a>(
+        &self,
+        operation: OperationRef<'c, 'a>,
+        rewriter: &mut PatternRewriter<'c, 'a>,
+    ) -> bool {
+        let name = operation.name();
+        let name = name.as_string_ref().as_str().ok();
+
+        self.patterns.iter().any(|pattern| {
+            (pattern.root_name().is_none() || pattern.root_name() == name)
+                && pattern.match_and_rewrite(operation, rewriter)
+        })
+    }
+
+    /// Returns `true` if a pattern set has native patterns, such as the ones
+    /// compiled from PDL pattern modules.
+    pub(crate) const fn has_native_patterns(&self) -> bool {
+        self.has_native_patterns
+    }
+
+    /// Converts a pattern set into a raw object of native patterns.
+    pub(crate) const fn to_native_raw(&self) -> MlirFrozenRewritePatternSet {
+        self.native
+    }
+}
+
+impl Drop for FrozenRewritePatternSet<'_> {
+    fn drop(&mut self) {
+        unsafe { mlirFrozenRewritePatternSetDestroy(self.native) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::create_test_context;
+
+    struct Noop;
+
+    impl<'c> RewritePattern<'c> for Noop {
+        fn match_and_rewrite<'a>(
+            &self,
+            _: OperationRef<'c, 'a>,
+            _: &mut PatternRewriter<'c, 'a>,
+        ) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+
+        assert!(RewritePatternSet::new(&context).is_empty());
+    }
+
+    #[test]
+    fn add() {
+        let context = create_test_context();
+        let mut patterns = RewritePatternSet::new(&context);
+
+        patterns.add(Noop).add(Noop);
+
+        assert_eq!(patterns.len(), 2);
+    }
+
+    #[test]
+    fn freeze() {
+        let context = create_test_context();
+        let mut patterns = RewritePatternSet::new(&context);
+
+        patterns.add(Noop);
+        patterns.freeze();
+    }
+}
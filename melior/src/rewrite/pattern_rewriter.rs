@@ -0,0 +1,251 @@
+use crate::{
+    context::{Context, ContextRef},
+    ir::{
+        operation::{OperationLike, OperationRefMut, WalkOrder, WalkResult},
+        BlockLike, BlockRef, Operation, OperationRef, Value, ValueLike,
+    },
+};
+use mlir_sys::{
+    mlirIRRewriterCreate, mlirIRRewriterDestroy, mlirRewriterBaseClearInsertionPoint,
+    mlirRewriterBaseEraseBlock, mlirRewriterBaseEraseOp, mlirRewriterBaseFinalizeOpModification,
+    mlirRewriterBaseGetContext, mlirRewriterBaseGetInsertionBlock, mlirRewriterBaseInsert,
+    mlirRewriterBaseReplaceAllUsesWith, mlirRewriterBaseReplaceOpWithOperation,
+    mlirRewriterBaseReplaceOpWithValues, mlirRewriterBaseSetInsertionPointAfter,
+    mlirRewriterBaseSetInsertionPointBefore, mlirRewriterBaseSetInsertionPointToEnd,
+    mlirRewriterBaseSetInsertionPointToStart, mlirRewriterBaseStartOpModification, MlirOperation,
+    MlirRewriterBase,
+};
+use std::{collections::HashSet, marker::PhantomData};
+
+/// A pattern rewriter.
+///
+/// Rewrite patterns must mutate IR only through a rewriter so that rewrite
+/// drivers can keep track of erased operations.
+pub struct PatternRewriter<'c, 'a> {
+    raw: MlirRewriterBase,
+    erased: HashSet<MlirOperation>,
+    _reference: PhantomData<&'a Operation<'c>>,
+}
+
+impl<'c, 'a> PatternRewriter<'c, 'a> {
+    /// Creates a pattern rewriter.
+    pub(crate) fn new(context: &'c Context) -> Self {
+        Self {
+            raw: unsafe { mlirIRRewriterCreate(context.to_raw()) },
+            erased: Default::default(),
+            _reference: Default::default(),
+        }
+    }
+
+    /// Returns a context.
+    pub fn context(&self) -> ContextRef<'c> {
+        unsafe { ContextRef::from_raw(mlirRewriterBaseGetContext(self.raw)) }
+    }
+
+    /// Sets an insertion point to just before an operation.
+    pub fn set_insertion_point_before(&mut self, operation: OperationRef<'c, 'a>) {
+        unsafe { mlirRewriterBaseSetInsertionPointBefore(self.raw, operation.to_raw()) }
+    }
+
+    /// Sets an insertion point to just after an operation.
+    pub fn set_insertion_point_after(&mut self, operation: OperationRef<'c, 'a>) {
+        unsafe { mlirRewriterBaseSetInsertionPointAfter(self.raw, operation.to_raw()) }
+    }
+
+    /// Sets an insertion point to the start of a block.
+    pub fn set_insertion_point_to_start(&mut self, block: BlockRef<'c, 'a>) {
+        unsafe { mlirRewriterBaseSetInsertionPointToStart(self.raw, block.to_raw()) }
+    }
+
+    /// Sets an insertion point to the end of a block.
+    pub fn set_insertion_point_to_end(&mut self, block: BlockRef<'c, 'a>) {
+        unsafe { mlirRewriterBaseSetInsertionPointToEnd(self.raw, block.to_raw()) }
+    }
+
+    /// Clears an insertion point.
+    pub fn clear_insertion_point(&mut self) {
+        unsafe { mlirRewriterBaseClearInsertionPoint(self.raw) }
+    }
+
+    /// Returns a block of the current insertion point.
+    pub fn insertion_block(&self) -> Option<BlockRef<'c, 'a>> {
+        unsafe { BlockRef::from_option_raw(mlirRewriterBaseGetInsertionBlock(self.raw)) }
+    }
+
+    /// Inserts an operation at the current insertion point.
+    pub fn insert(&mut self, operation: Operation<'c>) -> OperationRef<'c, 'a> {
+        unsafe { OperationRef::from_raw(mlirRewriterBaseInsert(self.raw, operation.into_raw())) }
+    }
+
+    /// Replaces results of an operation with values and erases the operation.
+    ///
+    /// The number of values must match the number of results.
+    pub fn replace_op(&mut self, operation: OperationRef<'c, 'a>, values: &[Value<'c, '_>]) {
+        self.mark_erased(operation);
+
+        unsafe {
+            mlirRewriterBaseReplaceOpWithValues(
+                self.raw,
+                operation.to_raw(),
+                values.len() as isize,
+                values.as_ptr() as *const _,
+            )
+        }
+    }
+
+    /// Replaces results of an operation with results of another operation and
+    /// erases the former.
+    pub fn replace_op_with_operation(
+        &mut self,
+        operation: OperationRef<'c, 'a>,
+        other: OperationRef<'c, 'a>,
+    ) {
+        self.mark_erased(operation);
+
+        unsafe {
+            mlirRewriterBaseReplaceOpWithOperation(self.raw, operation.to_raw(), other.to_raw())
+        }
+    }
+
+    /// Erases an operation.
+    ///
+    /// The operation must have no uses.
+    pub fn erase_op(&mut self, operation: OperationRef<'c, 'a>) {
+        self.mark_erased(operation);
+
+        unsafe { mlirRewriterBaseEraseOp(self.raw, operation.to_raw()) }
+    }
+
+    /// Erases a block with its operations.
+    pub fn erase_block(&mut self, block: BlockRef<'c, 'a>) {
+        let mut operation = block.first_operation();
+
+        while let Some(current) = operation {
+            self.mark_erased(current);
+            operation = current.next_in_block();
+        }
+
+        unsafe { mlirRewriterBaseEraseBlock(self.raw, block.to_raw()) }
+    }
+
+    /// Replaces all uses of a value with another value.
+    pub fn replace_all_uses_with(&mut self, from: Value<'c, '_>, to: Value<'c, '_>) {
+        unsafe { mlirRewriterBaseReplaceAllUsesWith(self.raw, from.to_raw(), to.to_raw()) }
+    }
+
+    /// Modifies an operation in place.
+    pub fn modify_op_in_place(
+        &mut self,
+        operation: OperationRef<'c, 'a>,
+        modify: impl FnOnce(OperationRefMut<'c, 'a>),
+    ) {
+        unsafe { mlirRewriterBaseStartOpModification(self.raw, operation.to_raw()) };
+
+        modify(unsafe { OperationRefMut::from_raw(operation.to_raw()) });
+
+        unsafe { mlirRewriterBaseFinalizeOpModification(self.raw, operation.to_raw()) }
+    }
+
+    /// Returns `true` if an operation has been erased by the rewriter.
+    pub(crate) fn is_erased(&self, operation: MlirOperation) -> bool {
+        self.erased.contains(&operation)
+    }
+
+    /// Forgets operations erased so far.
+    pub(crate) fn clear_erased(&mut self) {
+        self.erased.clear();
+    }
+
+    fn mark_erased(&mut self, operation: OperationRef<'c, 'a>) {
+        operation.walk(WalkOrder::PreOrder, |operation| {
+            self.erased.insert(operation.to_raw());
+            WalkResult::Advance
+        });
+    }
+}
+
+impl Drop for PatternRewriter<'_, '_> {
+    fn drop(&mut self) {
+        unsafe { mlirIRRewriterDestroy(self.raw) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::arith,
+        ir::{attribute::IntegerAttribute, r#type::IntegerType, Block, Location, Module},
+        test::create_test_context,
+    };
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+
+        PatternRewriter::new(&context);
+    }
+
+    #[test]
+    fn context() {
+        let context = create_test_context();
+
+        assert_eq!(PatternRewriter::new(&context).context(), context);
+    }
+
+    #[test]
+    fn insert() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let module = Module::new(location);
+        let mut rewriter = PatternRewriter::new(&context);
+
+        rewriter.set_insertion_point_to_end(module.body());
+
+        assert_eq!(rewriter.insertion_block(), Some(module.body()));
+
+        let operation = rewriter.insert(arith::constant(
+            &context,
+            IntegerAttribute::new(IntegerType::new(&context, 64).into(), 42).into(),
+            location,
+        ));
+
+        assert_eq!(module.body().first_operation(), Some(operation));
+        assert!(module.as_operation().verify());
+    }
+
+    #[test]
+    fn erase_op() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let module = Module::new(location);
+        let mut rewriter = PatternRewriter::new(&context);
+
+        let operation = module.body().append_operation(arith::constant(
+            &context,
+            IntegerAttribute::new(IntegerType::new(&context, 64).into(), 42).into(),
+            location,
+        ));
+
+        rewriter.erase_op(operation);
+
+        assert!(rewriter.is_erased(operation.to_raw()));
+        assert_eq!(module.body().first_operation(), None);
+
+        rewriter.clear_erased();
+
+        assert!(!rewriter.is_erased(operation.to_raw()));
+    }
+
+    #[test]
+    fn clear_insertion_point() {
+        let context = create_test_context();
+        let block = Block::new(&[]);
+        let mut rewriter = PatternRewriter::new(&context);
+
+        rewriter.set_insertion_point_to_start(unsafe { BlockRef::from_raw(block.to_raw()) });
+        rewriter.clear_insertion_point();
+
+        assert_eq!(rewriter.insertion_block(), None);
+    }
+}
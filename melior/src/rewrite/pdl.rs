@@ -0,0 +1,162 @@
+use super::{FrozenRewritePatternSet, RewritePatternSet};
+use crate::ir::Module;
+
+/// A PDL pattern module.
+///
+/// It holds a module of `pdl.pattern` operations, which are compiled into
+/// native rewrite patterns when a pattern set is frozen.
+///
+/// Native constraint and rewrite functions written in Rust cannot be
+/// registered for PDL patterns as the MLIR C API does not expose their
+/// registration. Patterns can only use `pdl.apply_native_constraint` and
+/// `pdl.apply_native_rewrite` with functions registered in C++.
+pub struct PdlPatternModule<'c> {
+    module: Module<'c>,
+}
+
+impl<'c> PdlPatternModule<'c> {
+    /// Creates a PDL pattern module from a module of `pdl.pattern` operations.
+    pub fn new(module: Module<'c>) -> Self {
+        Self { module }
+    }
+
+    /// Returns a module.
+    pub fn module(&self) -> &Module<'c> {
+        &self.module
+    }
+
+    /// Freezes a PDL pattern module into a pattern set.
+    pub fn freeze(self) -> FrozenRewritePatternSet<'c> {
+        let context = self.module.context();
+        let mut patterns = RewritePatternSet::new(unsafe { context.to_ref() });
+
+        patterns.add_pdl_module(self);
+        patterns.freeze()
+    }
+
+    pub(crate) fn into_module(self) -> Module<'c> {
+        self.module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::{operation::OperationLike, BlockLike},
+        rewrite::{apply_patterns_and_fold_greedily, GreedyRewriteConfig},
+        test::create_test_context,
+        Context,
+    };
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn create_pdl_module(context: &Context) -> PdlPatternModule<'_> {
+        PdlPatternModule::new(
+            Module::parse(
+                context,
+                indoc!(
+                    r#"
+                    pdl.pattern @muli_to_addi : benefit(1) {
+                      %type = pdl.type
+                      %lhs = pdl.operand
+                      %rhs = pdl.operand
+                      %op = pdl.operation "arith.muli"(%lhs, %rhs : !pdl.value, !pdl.value) -> (%type : !pdl.type)
+                      pdl.rewrite %op {
+                        %new = pdl.operation "arith.addi"(%lhs, %rhs : !pdl.value, !pdl.value) -> (%type : !pdl.type)
+                        pdl.replace %op with %new
+                      }
+                    }
+                    "#
+                ),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn apply(config: &GreedyRewriteConfig) -> String {
+        let context = create_test_context();
+        let mut module = Module::parse(
+            &context,
+            indoc!(
+                "
+                func.func @foo(%arg0 : i32, %arg1 : i32) -> i32 {
+                    %0 = arith.muli %arg0, %arg1 : i32
+                    return %0 : i32
+                }
+                "
+            ),
+        )
+        .unwrap();
+
+        apply_patterns_and_fold_greedily(
+            &mut module,
+            &create_pdl_module(&context).freeze(),
+            config,
+        )
+        .unwrap();
+
+        assert!(module.as_operation().verify());
+
+        module.as_operation().to_string()
+    }
+
+    #[test]
+    fn new() {
+        let context = create_test_context();
+
+        assert!(create_pdl_module(&context)
+            .module()
+            .body()
+            .first_operation()
+            .is_some());
+    }
+
+    #[test]
+    fn add_to_pattern_set() {
+        let context = create_test_context();
+        let mut patterns = RewritePatternSet::new(&context);
+
+        patterns.add_pdl_module(create_pdl_module(&context));
+
+        assert!(!patterns.is_empty());
+        assert_eq!(patterns.len(), 0);
+
+        patterns.freeze();
+    }
+
+    #[test]
+    fn freeze_modules_with_same_symbols() {
+        let context = create_test_context();
+        let mut patterns = RewritePatternSet::new(&context);
+
+        patterns
+            .add_pdl_module(create_pdl_module(&context))
+            .add_pdl_module(create_pdl_module(&context));
+
+        patterns.freeze();
+    }
+
+    #[test]
+    fn apply_greedily() {
+        let expected = indoc!(
+            "
+            module {
+              func.func @foo(%arg0: i32, %arg1: i32) -> i32 {
+                %0 = arith.addi %arg0, %arg1 : i32
+                return %0 : i32
+              }
+            }
+            "
+        );
+
+        assert_eq!(apply(&GreedyRewriteConfig::default()), expected);
+        assert_eq!(
+            apply(&GreedyRewriteConfig {
+                enable_region_simplification: false,
+                ..Default::default()
+            }),
+            expected
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::ir::{
+    operation::{OperationBuilder, OperationLike},
+    r#type::{FunctionType, TypeLike},
+    Location, Operation, OperationRef, Type, Value, ValueLike,
+};
+
+type Conversion<'c> = dyn Fn(Type<'c>) -> Option<Vec<Type<'c>>> + 'c;
+type Materialization<'c> =
+    dyn Fn(Type<'c>, &[Value<'c, '_>], Location<'c>) -> Option<Operation<'c>> + 'c;
+
+/// A type converter.
+///
+/// It converts types of values during dialect conversion. Conversions are
+/// tried in the reverse order of their addition.
+///
+/// Materializations build operations which convert values between original
+/// and converted types. If no materialization succeeds, a
+/// `builtin.unrealized_conversion_cast` operation is built instead.
+#[derive(Default)]
+pub struct TypeConverter<'c> {
+    conversions: Vec<Box<Conversion<'c>>>,
+    source_materializations: Vec<Box<Materialization<'c>>>,
+    target_materializations: Vec<Box<Materialization<'c>>>,
+}
+
+impl<'c> TypeConverter<'c> {
+    /// Creates a type converter without any conversion.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a conversion.
+    ///
+    /// A conversion returns converted types, or `None` if it does not handle a
+    /// given type. An empty list of types means that the type is dropped.
+    ///
+    /// Block arguments can be converted into any number of types. However,
+    /// conversion patterns only receive operands converted into single
+    /// types.
+    pub fn add_conversion(
+        &mut self,
+        conversion: impl Fn(Type<'c>) -> Option<Vec<Type<'c>>> + 'c,
+    ) -> &mut Self {
+        self.conversions.push(Box::new(conversion));
+        self
+    }
+
+    /// Adds a source materialization, which converts converted values back
+    /// into a value of an original type.
+    ///
+    /// It returns an operation whose first result is the original value.
+    pub fn add_source_materialization(
+        &mut self,
+        materialization: impl Fn(Type<'c>, &[Value<'c, '_>], Location<'c>) -> Option<Operation<'c>> + 'c,
+    ) -> &mut Self {
+        self.source_materializations.push(Box::new(materialization));
+        self
+    }
+
+    /// Adds a target materialization, which converts a value of an original
+    /// type into a value of a converted type.
+    ///
+    /// It returns an operation whose first result is the converted value.
+    pub fn add_target_materialization(
+        &mut self,
+        materialization: impl Fn(Type<'c>, &[Value<'c, '_>], Location<'c>) -> Option<Operation<'c>> + 'c,
+    ) -> &mut Self {
+        self.target_materializations.push(Box::new(materialization));
+        self
+    }
+
+    /// Converts a type.
+    ///
+    /// It returns `None` if no conversion handles the type.
+    pub fn convert_type(&self, r#type: Type<'c>) -> Option<Vec<Type<'c>>> {
+        self.conversions
+            .iter()
+            .rev()
+            .find_map(|conversion| conversion(r#type))
+    }
+
+    /// Returns `true` if a type is legal, which means that it is converted into
+    /// itself.
+    pub fn is_legal(&self, r#type: Type<'c>) -> bool {
+        self.convert_type(r#type) == Some(vec![r#type])
+    }
+
+    /// Converts a function type by converting its input and result types.
+    ///
+    /// It returns `None` if any of the types cannot be converted.
+    pub fn convert_function_type(&self, r#type: FunctionType<'c>) -> Option<FunctionType<'c>> {
+        let inputs = self.convert_types(
+            (0..r#type.input_count()).map(|index| r#type.input(index).expect("valid input")),
+        )?;
+        let results = self.convert_types(
+            (0..r#type.result_count()).map(|index| r#type.result(index).expect("valid result")),
+        )?;
+        let context = r#type.context();
+
+        Some(FunctionType::new(
+            unsafe { context.to_ref() },
+            &inputs,
+            &results,
+        ))
+    }
+
+    /// Returns `true` if all input and result types of a function type are
+    /// legal.
+    pub fn is_signature_legal(&self, r#type: FunctionType<'c>) -> bool {
+        self.convert_function_type(r#type)
+            .is_some_and(|converted| Type::from(converted) == Type::from(r#type))
+    }
+
+    /// Returns `true` if all operand and result types of an operation are
+    /// legal.
+    pub fn is_operation_legal(&self, operation: OperationRef<'c, '_>) -> bool {
+        operation
+            .operands()
+            .map(|operand| operand.r#type())
+            .chain(operation.results().map(|result| result.r#type()))
+            .all(|r#type| self.is_legal(r#type))
+    }
+
+    /// Builds an operation which converts values into a value of an original
+    /// type.
+    pub fn materialize_source(
+        &self,
+        r#type: Type<'c>,
+        inputs: &[Value<'c, '_>],
+        location: Location<'c>,
+    ) -> Operation<'c> {
+        Self::materialize(&self.source_materializations, r#type, inputs, location)
+    }
+
+    /// Builds an operation which converts values into a value of a converted
+    /// type.
+    pub fn materialize_target(
+        &self,
+        r#type: Type<'c>,
+        inputs: &[Value<'c, '_>],
+        location: Location<'c>,
+    ) -> Operation<'c> {
+        Self::materialize(&self.target_materializations, r#type, inputs, location)
+    }
+
+    fn convert_types(&self, types: impl Iterator<Item = Type<'c>>) -> Option<Vec<Type<'c>>> {
+        Some(
+            types
+                .map(|r#type| self.convert_type(r#type))
+                .collect::<Option<Vec<_>>>()?
+                .concat(),
+        )
+    }
+
+    fn materialize(
+        materializations: &[Box<Materialization<'c>>],
+        r#type: Type<'c>,
+        inputs: &[Value<'c, '_>],
+        location: Location<'c>,
+    ) -> Operation<'c> {
+        materializations
+            .iter()
+            .rev()
+            .find_map(|materialization| materialization(r#type, inputs, location))
+            .unwrap_or_else(|| {
+                OperationBuilder::new("builtin.unrealized_conversion_cast", location)
+                    .add_operands(inputs)
+                    .add_results(&[r#type])
+                    .build()
+                    .expect("valid unrealized conversion cast")
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::arith,
+        ir::{attribute::IntegerAttribute, r#type::IntegerType, Block, BlockLike},
+        test::create_test_context,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn convert_type() {
+        let context = create_test_context();
+        let index = Type::index(&context);
+        let i64 = IntegerType::new(&context, 64).into();
+        let mut converter = TypeConverter::new();
+
+        assert_eq!(converter.convert_type(index), None);
+
+        converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i64]));
+
+        assert_eq!(converter.convert_type(index), Some(vec![i64]));
+        assert_eq!(converter.convert_type(i64), Some(vec![i64]));
+        assert!(!converter.is_legal(index));
+        assert!(converter.is_legal(i64));
+    }
+
+    #[test]
+    fn convert_type_to_multiple_types() {
+        let context = create_test_context();
+        let index = Type::index(&context);
+        let i32 = IntegerType::new(&context, 32).into();
+        let mut converter = TypeConverter::new();
+
+        converter.add_conversion(move |_| Some(vec![i32, i32]));
+
+        assert_eq!(converter.convert_type(index), Some(vec![i32, i32]));
+    }
+
+    #[test]
+    fn is_operation_legal() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let index = Type::index(&context);
+        let mut converter = TypeConverter::new();
+
+        converter.add_conversion(move |r#type| (r#type != index).then(|| vec![r#type]));
+
+        let block = Block::new(&[]);
+        let operation = block.append_operation(arith::constant(
+            &context,
+            IntegerAttribute::new(index, 42).into(),
+            location,
+        ));
+
+        assert!(!converter.is_operation_legal(operation));
+
+        let operation = block.append_operation(arith::constant(
+            &context,
+            IntegerAttribute::new(IntegerType::new(&context, 64).into(), 42).into(),
+            location,
+        ));
+
+        assert!(converter.is_operation_legal(operation));
+    }
+
+    #[test]
+    fn materialize_with_unrealized_conversion_cast() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let index = Type::index(&context);
+        let i64 = IntegerType::new(&context, 64).into();
+        let block = Block::new(&[(index, location)]);
+
+        let operation = TypeConverter::new().materialize_target(
+            i64,
+            &[block.argument(0).unwrap().into()],
+            location,
+        );
+
+        assert_eq!(
+            operation.name().as_string_ref().as_str(),
+            Ok("builtin.unrealized_conversion_cast")
+        );
+        assert_eq!(operation.result(0).unwrap().r#type(), i64);
+    }
+
+    #[test]
+    fn materialize_source() {
+        let context = create_test_context();
+        let location = Location::unknown(&context);
+        let index = Type::index(&context);
+        let i64 = IntegerType::new(&context, 64).into();
+        let block = Block::new(&[(i64, location)]);
+        let mut converter = TypeConverter::new();
+
+        converter.add_source_materialization(|r#type, inputs, location| {
+            Some(arith::index_cast(inputs[0], r#type, location))
+        });
+
+        let operation =
+            converter.materialize_source(index, &[block.argument(0).unwrap().into()], location);
+
+        assert_eq!(
+            operation.name().as_string_ref().as_str(),
+            Ok("arith.index_cast")
+        );
+        assert_eq!(operation.result(0).unwrap().r#type(), index);
+    }
+
+    #[test]
+    fn convert_function_type() {
+        let context = create_test_context();
+        let index = Type::index(&context);
+        let i32 = IntegerType::new(&context, 32).into();
+        let mut converter = TypeConverter::new();
+
+        converter
+            .add_conversion(|r#type| Some(vec![r#type]))
+            .add_conversion(move |r#type| (r#type == index).then(|| vec![i32, i32]));
+
+        let r#type = FunctionType::new(&context, &[index, i32], &[index]);
+        let converted = converter.convert_function_type(r#type).unwrap();
+
+        assert_eq!(
+            Type::from(converted),
+            FunctionType::new(&context, &[i32, i32, i32], &[i32, i32]).into()
+        );
+        assert!(!converter.is_signature_legal(r#type));
+        assert!(converter.is_signature_legal(converted));
+    }
+}
@@ -5,8 +5,8 @@ use crate::{
     string_ref::StringRef, Error,
 };
 use mlir_sys::{
-    mlirLoadIRDLDialects, mlirParsePassPipeline, mlirRegisterAllDialects,
-    mlirRegisterAllLLVMTranslations, mlirRegisterAllPasses, MlirStringRef,
+    mlirLoadIRDLDialects, mlirOpPassManagerAddPipeline, mlirParsePassPipeline,
+    mlirRegisterAllDialects, mlirRegisterAllLLVMTranslations, mlirRegisterAllPasses, MlirStringRef,
 };
 use std::{
     ffi::c_void,
@@ -15,6 +15,9 @@ use std::{
 };
 
 /// Registers all dialects to a dialect registry.
+///
+/// It also registers all dialect extensions, such as the ones which add
+/// operations to the `transform` dialect.
 pub fn register_all_dialects(registry: &DialectRegistry) {
     unsafe { mlirRegisterAllDialects(registry.to_raw()) }
 }
@@ -33,7 +36,18 @@ pub fn register_all_passes() {
 }
 
 /// Parses a pass pipeline.
+///
+/// It can reference external passes registered with
+/// [`register_external_pass`](pass::register_external_pass).
 pub fn parse_pass_pipeline(manager: pass::OperationPassManager, source: &str) -> Result<(), Error> {
+    pass::parse_external_pass_pipeline(manager, source)
+        .unwrap_or_else(|| parse_native_pass_pipeline(manager, source))
+}
+
+pub(crate) fn parse_native_pass_pipeline(
+    manager: pass::OperationPassManager,
+    source: &str,
+) -> Result<(), Error> {
     let mut error_message = None;
 
     let result = LogicalResult::from_raw(unsafe {
@@ -45,6 +59,28 @@ pub fn parse_pass_pipeline(manager: pass::OperationPassManager, source: &str) ->
         )
     });
 
+    convert_parse_result(result, error_message)
+}
+
+pub(crate) fn add_native_pass_pipeline(
+    manager: pass::OperationPassManager,
+    source: &str,
+) -> Result<(), Error> {
+    let mut error_message = None;
+
+    let result = LogicalResult::from_raw(unsafe {
+        mlirOpPassManagerAddPipeline(
+            manager.to_raw(),
+            StringRef::new(source).to_raw(),
+            Some(handle_parse_error),
+            &mut error_message as *mut _ as *mut _,
+        )
+    });
+
+    convert_parse_result(result, error_message)
+}
+
+fn convert_parse_result(result: LogicalResult, error_message: Option<String>) -> Result<(), Error> {
     if result.is_success() {
         Ok(())
     } else {
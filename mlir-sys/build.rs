@@ -340,6 +340,16 @@ fn generate_bindings(config: &MlirConfig) {
         .allowlist_var("MLIR.*")
         .allowlist_type("MlirLlvmThreadPool")
         .allowlist_function("mlirLlvm.*")
+        // Allow LLVM C API symbols for LLVM modules translated from MLIR
+        .allowlist_function("LLVMContextCreate")
+        .allowlist_function("LLVMContextDispose")
+        .allowlist_function("LLVMDisposeModule")
+        .allowlist_function("LLVMPrintModuleToString")
+        .allowlist_function("LLVMDisposeMessage")
+        .allowlist_function("LLVMWriteBitcodeToMemoryBuffer")
+        .allowlist_function("LLVMGetBufferStart")
+        .allowlist_function("LLVMGetBufferSize")
+        .allowlist_function("LLVMDisposeMemoryBuffer")
         // Block inline functions (reimplemented in Rust)
         .blocklist_function("mlirStringRefCreate")
         .blocklist_function("mlirLogicalResultIsSuccess")
@@ -25,6 +25,11 @@
 #include "mlir-c/Conversion.h"
 #include "mlir-c/RegisterEverything.h"
 #include "mlir-c/ExecutionEngine.h"
+#include "mlir-c/Target/LLVMIR.h"
+
+/* LLVM C API for translated LLVM modules */
+#include "llvm-c/BitWriter.h"
+#include "llvm-c/Core.h"
 
 /* All dialect headers - included for full melior compatibility */
 #include "mlir-c/Dialect/Func.h"
@@ -45,6 +50,7 @@
 #include "mlir-c/Dialect/Shape.h"
 #include "mlir-c/Dialect/SparseTensor.h"
 #include "mlir-c/Dialect/Transform.h"
+#include "mlir-c/Dialect/Transform/Interpreter.h"
 #include "mlir-c/Dialect/SPIRV.h"
 #include "mlir-c/Dialect/NVVM.h"
 #include "mlir-c/Dialect/ROCDL.h"